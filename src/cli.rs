@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::enums::{AppMode, LogLev, SimpleDataCollectionMode};
 
@@ -63,6 +63,14 @@ pub struct Cli {
     #[arg(short = 'z', long, default_value = "false", value_name = "WHITE_PLOT_MODE", help = "White plot mode.")]
     pub white_plot_mode: bool,
 
+    #[arg(
+        long,
+        default_value = "false",
+        value_name = "STACKED_MEMORY_PLOT",
+        help = "Show free, anonymous, buffers, cached and slab memory as stacked area, which sums up to total memory."
+    )]
+    pub stacked_memory_plot: bool,
+
     #[arg(short, long, default_value = "info", value_name = "Info", help = "Logging level")]
     pub log_level: LogLev,
 
@@ -102,13 +110,176 @@ pub struct Cli {
     )]
     pub maximum_data_file_size_mb: f32,
 
+    #[arg(
+        short = 'n',
+        long,
+        default_value = "false",
+        value_name = "SKIP_VIRTUAL_NETWORK_INTERFACES",
+        help = "Skip loopback and virtual network interfaces(bridges, veth, tun etc.) when collecting network data."
+    )]
+    pub skip_virtual_network_interfaces: bool,
+
+    #[arg(
+        long,
+        default_value = "/sys",
+        value_name = "SYSFS_PATH",
+        help = "Path to sysfs filesystem, from which cpu frequency, temperatures and other hardware info is read. Useful mostly for testing with fake directory tree."
+    )]
+    pub sysfs_path: String,
+
+    #[arg(
+        long,
+        value_name = "TCP_LOCAL_PORT",
+        help = "Count only TCP connections with this local port in tcp-* modes e.g. 443, by default all connections are counted"
+    )]
+    pub tcp_local_ports: Vec<u16>,
+
+    #[arg(
+        long,
+        value_name = "USER",
+        help = "User name or uid, of which summed cpu and memory usage of all processes is collected in user-cpu and user-memory modes, by default users with the highest usage are collected"
+    )]
+    pub users_to_check: Vec<String>,
+
+    #[arg(
+        long,
+        default_value = "5",
+        value_name = "TOP_USERS",
+        help = "Number of users with the highest cpu usage collected in user-cpu and user-memory modes, when --users-to-check is not used"
+    )]
+    pub top_users: usize,
+
+    #[arg(
+        long,
+        default_value = "0",
+        value_name = "TOP_PROCESSES",
+        help = "Save this number of processes with the highest cpu and memory usage into separate file(data file name with _top_processes suffix), which is shown when hovering total cpu usage and used memory in plot. 0 disables it."
+    )]
+    pub top_processes: usize,
+
+    #[arg(
+        long,
+        default_value = "1",
+        value_name = "TICKS",
+        help = "Save top processes only every TICKS ticks, cpu usage is then averaged over all ticks since previous save."
+    )]
+    pub top_processes_interval: usize,
+
     #[arg(
         short = 'e',
         long,
         value_name = "CMD_SEARCH_TEXT",
-        help = "Search for certain text in process run command"
+        help = "Search for process to track, in format \"NAME|SELECTOR\". Selector may be text searched in process run command or pid:PID, name:PROCESS_NAME, exe:EXECUTABLE_PATH, user:USER_NAME_OR_UID, cwd:WORKING_DIRECTORY, regex:CMD_REGEX, cmd:TEXT(required when text contains :). Name and selector cannot contain , and =, in regex use \\x2c and \\x3d instead"
     )]
     pub process_cmd_to_search: Vec<String>,
+
+    #[arg(
+        short = 't',
+        long,
+        value_name = "CMD_SEARCH_TEXT",
+        help = "The same as -e, but cpu and memory usage of all children of found process is added to its usage"
+    )]
+    pub process_tree_to_search: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "CMD_SEARCH_TEXT",
+        help = "The same as -e, but every process containing text in run command is tracked as separate series, instead only one with shortest command. Useful to compare multiple instances of worker. Extended and PSS metrics are not collected for them."
+    )]
+    pub all_processes_to_search: Vec<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        value_name = "SUM_ALL_PROCESSES",
+        help = "Save also summed cpu and memory usage of all processes found by --all-processes-to-search."
+    )]
+    pub sum_all_processes: bool,
+
+    #[arg(
+        long,
+        value_name = "SELECTOR",
+        help = "Processes matching this selector(in the same format as in -e) are never tracked e.g. name:grep or name:bash"
+    )]
+    pub process_exclusions: Vec<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        value_name = "PROCESS_EVENTS",
+        help = "Save start and exit of every process(without kernel threads) into separate file(data file name with _process_events suffix), which are shown as markers in plot. Processes which started and ended between ticks are not visible."
+    )]
+    pub process_events: bool,
+
+    #[arg(
+        long,
+        value_name = "SELECTOR",
+        help = "Save process events only of processes matching this selector(in the same format as in -e) e.g. name:nginx, implies --process-events"
+    )]
+    pub process_events_filters: Vec<String>,
+
+    #[arg(
+        short = 'x',
+        long,
+        default_value = "false",
+        value_name = "EXTENDED_PROCESS_METRICS",
+        help = "Collect also threads, open files, virtual memory, context switches and disk read/write speed of processes found by -e."
+    )]
+    pub extended_process_metrics: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        value_name = "PROCESS_PSS_MEMORY",
+        help = "Collect also proportional memory usage(PSS) of processes found by -e, split into anonymous, file and shared memory. Memory shared between processes is split between them, so it is not counted multiple times like in RSS."
+    )]
+    pub process_pss_memory: bool,
+
+    #[arg(
+        short = 's',
+        long,
+        value_name = "MOUNT_POINT",
+        help = "Mount point of which used/available disk space and inode usage will be collected e.g. /var"
+    )]
+    pub mount_points_to_check: Vec<String>,
+
+    #[arg(
+        short = 'g',
+        long,
+        value_name = "CGROUP",
+        help = "Cgroup v2 of which memory, cpu, throttling and io usage will be collected, relative to cgroup root e.g. \"NGINX|system.slice/nginx.service\""
+    )]
+    pub cgroups_to_check: Vec<String>,
+
+    #[arg(
+        long,
+        default_value = "/sys/fs/cgroup",
+        value_name = "CGROUP_PATH",
+        help = "Path to cgroup v2 filesystem root. Useful mostly for testing with fake directory tree."
+    )]
+    pub cgroup_path: String,
+
+    #[arg(
+        long,
+        default_value = "false",
+        value_name = "SCALE_TO_CGROUP_LIMITS",
+        help = "When app was running inside container or other cgroup with memory/cpu limits, scale memory axis to memory limit and show cpu usage of cgroups as percentage of cpu limit."
+    )]
+    pub scale_to_cgroup_limits: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    #[command(
+        about = "Run command and monitor it together with all its children, e.g. run -- ./my_benchmark --threads 4. Collecting stops when it exits and its exit status and wall time are saved into data file."
+    )]
+    Run {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
 }
 
 pub(crate) fn parse_cli() -> Cli {
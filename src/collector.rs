@@ -1,32 +1,141 @@
 use anyhow::{Context, Error};
 use crossbeam_channel::unbounded;
 use log::{debug, info};
-use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tokio::process::{Child, Command};
 use tokio::time::interval;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::{BufWriter, Write};
+use std::os::unix::process::ExitStatusExt;
 use std::path::Path;
 use std::time::{Duration, Instant, SystemTime};
 
 use crate::enums::{DataType, HeaderValues, SimpleDataCollectionMode};
-use crate::model::{CustomProcessData, ProcessCache, Settings};
+use crate::model::{CustomProcessData, FindingStruct, ProcessCache, ProcessSelector, Settings};
 use crate::ploty_creator::load_results_and_save_plot;
 use crate::set_ctrl_c_handler;
+use crate::stats::cgroup::{read_effective_limits, CgroupValues};
+use crate::stats::descriptors::DescriptorsStatsCache;
+use crate::stats::disk::DiskRates;
+use crate::stats::network::NetworkRates;
+use crate::stats::power_supply::PowerSupplyValues;
+use crate::stats::pressure::PressureStatsCache;
+use crate::stats::proc_stat::{CpuStates, KernelActivity};
+use crate::stats::process_details::{read_smaps_rollup, ProcessDetails, SmapsRollup};
+use crate::stats::process_events::ProcessEvent;
+use crate::stats::processes::ProcessesStatsCache;
+use crate::stats::tasks::{find_descendants, TasksStatsCache};
+use crate::stats::tcp_states::TcpStates;
+use crate::stats::top_processes::TopProcessesSnapshot;
+use crate::stats::users::UserUsage;
+use crate::stats::vmstat::VmStatRates;
+use crate::stats::{sanitize_item_name, SystemStatsCache};
 
-pub async fn collect_data(sys: &mut System, settings: &Settings) -> Result<(), Error> {
-    backup_old_file(settings)?;
+// Marks last line of data file with exit of command, so it is not mistaken for data line
+pub const FOOTER_LINE_PREFIX: &str = "FOOTER,";
 
-    let data_file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(&settings.data_path)
-        .context(format!("Failed to open data file {}", settings.data_path))?;
-    let mut data_file = BufWriter::new(data_file);
-    write_header_into_file(sys, &mut data_file, settings)?;
+// Command given by user, which is waited for together with interval, to get exact wall time
+pub struct MonitoredCommand {
+    child: Child,
+    start_time: Instant,
+}
+
+struct CommandExit {
+    status: String,
+    wall_time: Duration,
+}
+
+impl MonitoredCommand {
+    async fn wait_for_exit(&mut self) -> CommandExit {
+        let status = match self.child.wait().await {
+            Ok(status) => match (status.code(), status.signal()) {
+                (Some(code), _) => code.to_string(),
+                (None, Some(signal)) => format!("signal {signal}"),
+                (None, None) => "unknown".to_string(),
+            },
+            Err(_) => "unknown".to_string(),
+        };
+        CommandExit {
+            status,
+            wall_time: self.start_time.elapsed(),
+        }
+    }
+
+    // Used when collecting failed, so command is not left running without anyone monitoring it
+    async fn kill(&mut self) {
+        if let Err(e) = self.child.kill().await {
+            debug!("Failed to kill monitored command - {e}");
+        }
+    }
+
+    // Used when app is closed with Ctrl-C, command is killed and waited for, so its exit can be still saved
+    async fn stop(&mut self) -> CommandExit {
+        if let Err(e) = self.child.start_kill() {
+            debug!("Failed to kill monitored command - {e}");
+        }
+        self.wait_for_exit().await
+    }
+}
+
+// Started after first samples of system and processes were taken, so usage since its start is fully counted
+// Its output is not captured, so it is visible in the same terminal as app logs
+fn start_monitored_command(settings: &mut Settings) -> Result<Option<MonitoredCommand>, Error> {
+    let Some((program, args)) = settings.command_to_run.split_first() else {
+        return Ok(None);
+    };
+    let command_text = settings.command_to_run.join(" ");
+    let start_time = Instant::now();
+    // Dropping child e.g. when app panics, kills command, instead of leaving it running in background
+    let child = Command::new(program)
+        .args(args)
+        .kill_on_drop(true)
+        .spawn()
+        .context(format!("Failed to run command \"{command_text}\""))?;
+    // Id is available until child is awaited, so this cannot fail here
+    let pid = child.id().context(format!("Failed to get pid of command \"{command_text}\""))?;
+    info!("Started command \"{command_text}\" with pid \"{pid}\"");
+
+    let graph_name = Path::new(program)
+        .file_name()
+        .map_or_else(|| program.clone(), |e| e.to_string_lossy().to_string());
+    settings.process_cmd_to_search.push(FindingStruct {
+        graph_name: sanitize_item_name(&graph_name),
+        selector: ProcessSelector::Pid(pid as usize),
+        include_children: true,
+        all_matching: false,
+    });
+
+    Ok(Some(MonitoredCommand { child, start_time }))
+}
+
+pub async fn collect_data(sys: &mut System, settings: &mut Settings) -> Result<(), Error> {
+    backup_old_file(&settings.data_path, settings)?;
+    let data_file = create_data_file(&settings.data_path)?;
+    let stats_cache = SystemStatsCache::new(sys, settings)?;
+
+    let mut monitored_command = start_monitored_command(settings)?;
+    let result = collect_data_into_file(sys, settings, data_file, stats_cache, monitored_command.as_mut()).await;
+    if result.is_err() {
+        if let Some(monitored_command) = &mut monitored_command {
+            monitored_command.kill().await;
+        }
+    }
+    result
+}
+
+async fn collect_data_into_file(
+    sys: &mut System,
+    settings: &Settings,
+    mut data_file: BufWriter<fs::File>,
+    mut stats_cache: SystemStatsCache,
+    mut monitored_command: Option<&mut MonitoredCommand>,
+) -> Result<(), Error> {
+    write_header_into_file(sys, &mut data_file, settings, &stats_cache)?;
+
+    let mut sidecar_files = SidecarFiles::new(settings)?;
 
     let mut interv = interval(Duration::from_millis((settings.check_interval * 1000.0) as u64));
     interv.tick().await; // This will instantly finish, so next time will take required amount of seconds
@@ -39,28 +148,126 @@ pub async fn collect_data(sys: &mut System, settings: &Settings) -> Result<(), E
 
     info!("Started collecting data...");
     loop {
-        collect_and_save_data(sys, &mut data_file, settings, &mut collected_bytes, &mut process_cache_data)?;
+        // First row is saved one interval after stats cache took its first sample, so rates in it are computed over
+        // whole interval, not over few milliseconds spent on writing header
+        // Exit of command ends waiting for next tick, so wall time is exact and last sample is taken right after exit
+        let mut command_exit = tokio::select! {
+            _ = interv.tick() => None,
+            command_exit = wait_for_command_exit(monitored_command.as_deref_mut()) => {
+                info!(
+                    "Command exited with status \"{}\" after {:?}, stopping collecting data",
+                    command_exit.status, command_exit.wall_time
+                );
+                Some(command_exit)
+            }
+        };
+
+        collect_and_save_data(
+            sys,
+            &mut data_file,
+            settings,
+            &mut collected_bytes,
+            &mut process_cache_data,
+            &mut stats_cache,
+            &mut sidecar_files,
+        )?;
+
+        let ctrl_c_clicked = crx.try_recv().is_ok();
+        if ctrl_c_clicked && command_exit.is_none() {
+            if let Some(monitored_command) = monitored_command.as_deref_mut() {
+                info!("Killing command, because app is closing");
+                command_exit = Some(monitored_command.stop().await);
+            }
+        }
 
-        if crx.try_recv().is_ok() {
+        if let Some(command_exit) = &command_exit {
+            write_command_exit_into_file(&mut data_file, settings, command_exit)?;
+        }
+
+        if command_exit.is_some() || ctrl_c_clicked {
             drop(data_file);
+            drop(sidecar_files);
             if settings.app_mode == crate::enums::AppMode::COLLECT_AND_CONVERT {
                 load_results_and_save_plot(settings)?;
             }
             return Ok(());
         }
+    }
+}
 
-        interv.tick().await;
+// Without command, this never finishes, so only interval is waited for
+async fn wait_for_command_exit(monitored_command: Option<&mut MonitoredCommand>) -> CommandExit {
+    match monitored_command {
+        Some(monitored_command) => monitored_command.wait_for_exit().await,
+        None => std::future::pending().await,
+    }
+}
+
+// Data without constant number of columns is saved in separate files, linked with data file by SECONDS_SINCE_START value
+struct SidecarFiles {
+    top_processes: Option<SidecarFile>,
+    process_events: Option<SidecarFile>,
+}
+
+impl SidecarFiles {
+    fn new(settings: &Settings) -> Result<Self, Error> {
+        let top_processes = if settings.top_processes > 0 {
+            let header = format!("{},TOP_CPU,TOP_MEMORY", DataType::SECONDS_SINCE_START);
+            Some(SidecarFile::create(get_top_processes_path(&settings.data_path), &header, settings)?)
+        } else {
+            None
+        };
+        let process_events = if settings.process_events {
+            let header = format!("{},EVENT,PID,PPID,NAME,LIFETIME_SECONDS,CMD", DataType::SECONDS_SINCE_START);
+            Some(SidecarFile::create(get_process_events_path(&settings.data_path), &header, settings)?)
+        } else {
+            None
+        };
+        Ok(SidecarFiles {
+            top_processes,
+            process_events,
+        })
     }
 }
 
-// Function to create
-fn backup_old_file(settings: &Settings) -> Result<(), Error> {
+struct SidecarFile {
+    path: String,
+    file: BufWriter<fs::File>,
+}
+
+impl SidecarFile {
+    fn create(path: String, header: &str, settings: &Settings) -> Result<Self, Error> {
+        backup_old_file(&path, settings)?;
+        let mut file = create_data_file(&path)?;
+        writeln!(file, "{header}").context(format!("Failed to write header into file {path}"))?;
+        if !settings.disable_instant_flushing {
+            file.flush().context(format!("Failed to flush file {path}"))?;
+        }
+        Ok(SidecarFile { path, file })
+    }
+
+    fn write_lines(&mut self, lines: &[String], settings: &Settings) -> Result<(), Error> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        for line in lines {
+            writeln!(self.file, "{line}").context(format!("Failed to write data into file {}", self.path))?;
+        }
+        if !settings.disable_instant_flushing {
+            self.file.flush().context(format!("Failed to flush file {}", self.path))?;
+        }
+        Ok(())
+    }
+}
+
+// Rotates backups of data file or other file created by app
+fn backup_old_file(file_path: &str, settings: &Settings) -> Result<(), Error> {
     if settings.backup_number == 0 {
         return Ok(()); // No backup required
     }
     let mut backup_file_names = vec![];
     for i in 1..=settings.backup_number {
-        backup_file_names.push(format_new_name(&settings.data_path, &format!("__{i}")));
+        backup_file_names.push(format_new_name(file_path, &format!("__{i}")));
     }
 
     // Remove last backup file
@@ -82,11 +289,8 @@ fn backup_old_file(settings: &Settings) -> Result<(), Error> {
     }
 
     // Rename current file into first backup file name
-    if Path::new(&settings.data_path).exists() {
-        fs::rename(&settings.data_path, &backup_file_names[0]).context(format!(
-            "Failed to rename data file {} into {}",
-            &settings.data_path, &backup_file_names[0]
-        ))?;
+    if Path::new(file_path).exists() {
+        fs::rename(file_path, &backup_file_names[0]).context(format!("Failed to rename data file {file_path} into {}", &backup_file_names[0]))?;
     }
 
     info!("Backup files renamed successfully");
@@ -94,6 +298,40 @@ fn backup_old_file(settings: &Settings) -> Result<(), Error> {
     Ok(())
 }
 
+fn create_data_file(file_path: &str) -> Result<BufWriter<fs::File>, Error> {
+    let data_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(file_path)
+        .context(format!("Failed to open data file {file_path}"))?;
+    Ok(BufWriter::new(data_file))
+}
+
+// e.g. system_data.csv -> system_data_top_processes.csv
+pub fn get_top_processes_path(data_path: &str) -> String {
+    format_new_name(data_path, "_top_processes")
+}
+
+pub fn get_process_events_path(data_path: &str) -> String {
+    format_new_name(data_path, "_process_events")
+}
+
+// Saved as last line, because it is known only when collecting ends
+fn write_command_exit_into_file(data_file: &mut BufWriter<fs::File>, settings: &Settings, command_exit: &CommandExit) -> Result<(), Error> {
+    writeln!(
+        data_file,
+        "{FOOTER_LINE_PREFIX}{}={},{}={:.2}",
+        HeaderValues::COMMAND_EXIT_STATUS,
+        command_exit.status,
+        HeaderValues::COMMAND_WALL_TIME_SECONDS,
+        command_exit.wall_time.as_secs_f64()
+    )
+    .context(format!("Failed to write command exit status into data file {}", settings.data_path))?;
+    data_file.flush().context(format!("Failed to flush data file {}", settings.data_path))?;
+    Ok(())
+}
+
 fn format_new_name(file_path: &str, item_to_add: &str) -> String {
     if let Some(index) = file_path.rfind('.') {
         let (base, extension) = file_path.split_at(index);
@@ -103,14 +341,94 @@ fn format_new_name(file_path: &str, item_to_add: &str) -> String {
     }
 }
 
-fn write_header_into_file(sys: &mut System, data_file: &mut BufWriter<std::fs::File>, settings: &Settings) -> Result<(), Error> {
-    let custom_headers = settings
+fn write_header_into_file(
+    sys: &mut System,
+    data_file: &mut BufWriter<std::fs::File>,
+    settings: &Settings,
+    stats_cache: &SystemStatsCache,
+) -> Result<(), Error> {
+    let mut custom_headers = settings
         .process_cmd_to_search
         .iter()
         .enumerate()
-        .map(|(idx, e)| format!("CUSTOM_{idx}={}", e.graph_name))
-        .collect::<Vec<_>>()
-        .join(",");
+        .flat_map(|(idx, e)| [format!("CUSTOM_{idx}={}", e.graph_name), format!("CUSTOM_{idx}_SELECTOR={}", e.selector)])
+        .collect::<Vec<_>>();
+    custom_headers.extend(
+        settings
+            .process_exclusions
+            .iter()
+            .enumerate()
+            .map(|(idx, e)| format!("PROCESS_EXCLUSION_{idx}={e}")),
+    );
+    custom_headers.extend(
+        settings
+            .mount_points_to_check
+            .iter()
+            .enumerate()
+            .map(|(idx, mount_point)| format!("MOUNT_{idx}={mount_point}")),
+    );
+    custom_headers.extend(
+        settings
+            .cgroups_to_check
+            .iter()
+            .enumerate()
+            .map(|(idx, e)| format!("CGROUP_{idx}={}", e.graph_name)),
+    );
+
+    let effective_limits = read_effective_limits(&settings.cgroup_path);
+    if let Some(memory_limit) = effective_limits.memory_bytes {
+        custom_headers.push(format!(
+            "{}={}",
+            HeaderValues::EFFECTIVE_MEMORY_LIMIT,
+            convert_into_string_megabytes(memory_limit)
+        ));
+    }
+    if let Some(cpu_limit) = effective_limits.cpu_cores {
+        custom_headers.push(format!("{}={cpu_limit:.2}", HeaderValues::EFFECTIVE_CPU_LIMIT));
+    }
+    if settings.top_processes > 0 {
+        custom_headers.push(format!("{}={}", HeaderValues::TOP_PROCESSES, settings.top_processes));
+    }
+    if !settings.command_to_run.is_empty() {
+        custom_headers.push(format!(
+            "{}={}",
+            HeaderValues::COMMAND,
+            sanitize_item_name(&settings.command_to_run.join(" "))
+        ));
+    }
+    if settings.process_events {
+        custom_headers.push(format!("{}=true", HeaderValues::PROCESS_EVENTS));
+        custom_headers.extend(
+            settings
+                .process_events_filters
+                .iter()
+                .enumerate()
+                .map(|(idx, e)| format!("PROCESS_EVENTS_FILTER_{idx}={e}")),
+        );
+    }
+
+    if let Some(disk) = &stats_cache.disk {
+        custom_headers.push(format_header_list(HeaderValues::DISK_DEVICES, &disk.devices));
+    }
+    if let Some(network) = &stats_cache.network {
+        custom_headers.push(format_header_list(HeaderValues::NETWORK_INTERFACES, &network.interfaces));
+    }
+    if let Some(tcp_states) = &stats_cache.tcp_states {
+        if !tcp_states.local_ports.is_empty() {
+            custom_headers.push(format_header_list(
+                HeaderValues::TCP_LOCAL_PORTS,
+                &tcp_states.local_ports.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ));
+        }
+    }
+    if let Some(temperature) = &stats_cache.temperature {
+        custom_headers.push(format_header_list(HeaderValues::TEMPERATURE_SENSORS, &temperature.get_sensor_names()));
+    }
+    if let Some(power_supply) = &stats_cache.power_supply {
+        custom_headers.push(format_header_list(HeaderValues::POWER_SUPPLIES, &power_supply.get_supply_names()));
+    }
+
+    let custom_headers = custom_headers.join(",");
 
     let custom_headers = if custom_headers.is_empty() {
         String::new()
@@ -137,7 +455,11 @@ fn write_header_into_file(sys: &mut System, data_file: &mut BufWriter<std::fs::F
     writeln!(data_file, "{general_info}").context(format!("Failed to write general into data file {}", settings.data_path))?;
 
     let custom_columns = (0..settings.process_cmd_to_search.len())
-        .map(|idx| format!("CUSTOM_{idx}_CPU,CUSTOM_{idx}_MEMORY"))
+        .map(|idx| format_custom_process_columns(idx, settings))
+        .chain((0..settings.mount_points_to_check.len()).map(|idx| format!("MOUNT_{idx}_USED,MOUNT_{idx}_AVAILABLE,MOUNT_{idx}_INODES_USED")))
+        .chain((0..settings.cgroups_to_check.len()).map(|idx| {
+            format!("CGROUP_{idx}_MEMORY,CGROUP_{idx}_MEMORY_MAX,CGROUP_{idx}_CPU,CGROUP_{idx}_THROTTLED,CGROUP_{idx}_IO_READ,CGROUP_{idx}_IO_WRITE")
+        }))
         .collect::<Vec<_>>()
         .join(",");
     let custom_columns = if custom_columns.is_empty() {
@@ -165,21 +487,82 @@ fn write_header_into_file(sys: &mut System, data_file: &mut BufWriter<std::fs::F
     Ok(())
 }
 
+fn format_custom_process_columns(idx: usize, settings: &Settings) -> String {
+    if settings.process_cmd_to_search[idx].all_matching {
+        let mut kinds = vec!["CPU_PER_PID", "MEMORY_PER_PID"];
+        if settings.sum_all_processes {
+            kinds.extend(["CPU", "MEMORY"]);
+        }
+        return kinds.iter().map(|kind| format!("CUSTOM_{idx}_{kind}")).collect::<Vec<_>>().join(",");
+    }
+
+    let mut kinds = vec!["CPU", "MEMORY"];
+    if settings.extended_process_metrics {
+        kinds.extend([
+            "THREADS",
+            "OPEN_FILES",
+            "VIRTUAL_MEMORY",
+            "VOLUNTARY_CONTEXT_SWITCHES",
+            "INVOLUNTARY_CONTEXT_SWITCHES",
+            "DISK_READ",
+            "DISK_WRITE",
+        ]);
+    }
+    if settings.process_pss_memory {
+        kinds.extend(["PSS", "PSS_ANON", "PSS_FILE", "PSS_SHMEM", "SWAP_PSS"]);
+    }
+    kinds.iter().map(|kind| format!("CUSTOM_{idx}_{kind}")).collect::<Vec<_>>().join(",")
+}
+
+fn format_all_processes(processes: &[CustomProcessData], sys: &System, settings: &Settings) -> Vec<String> {
+    let mut values = vec![
+        processes
+            .iter()
+            .filter_map(|e| Some(format!("{}:{:.2}", e.pid, e.cpu_usage? / sys.cpus().len() as f32)))
+            .collect::<Vec<_>>()
+            .join(";"),
+        processes
+            .iter()
+            .map(|e| format!("{}:{}", e.pid, convert_into_string_megabytes(e.memory_usage)))
+            .collect::<Vec<_>>()
+            .join(";"),
+    ];
+    if settings.sum_all_processes {
+        // Usage is unknown only when none of processes has known usage yet, no processes means no usage
+        let cpu_usages = processes.iter().filter_map(|e| e.cpu_usage).collect::<Vec<_>>();
+        let cpu_usage_sum = match (processes.is_empty(), cpu_usages.is_empty()) {
+            (true, _) => Some(0.0),
+            (false, true) => None,
+            (false, false) => Some(f64::from(cpu_usages.iter().sum::<f32>())),
+        };
+        values.push(format_optional_value(cpu_usage_sum.map(|e| e / sys.cpus().len() as f64)));
+        values.push(convert_into_string_megabytes(processes.iter().map(|e| e.memory_usage).sum()));
+    }
+    values
+}
+
+fn format_header_list(header: HeaderValues, items: &[String]) -> String {
+    format!("{header}={}", items.iter().map(|e| sanitize_item_name(e)).collect::<Vec<_>>().join(";"))
+}
+
 fn collect_and_save_data(
     sys: &mut System,
     data_file: &mut BufWriter<fs::File>,
     settings: &Settings,
     collected_bytes: &mut usize,
     process_cache_data: &mut ProcessCache,
+    stats_cache: &mut SystemStatsCache,
+    sidecar_files: &mut SidecarFiles,
 ) -> Result<(), Error> {
     let current_time = SystemTime::now();
 
     let start = Instant::now();
     sys.refresh_cpu_usage();
     sys.refresh_memory();
+    stats_cache.refresh()?;
 
     if settings.need_to_refresh_processes {
-        check_for_new_and_old_process_data(sys, process_cache_data, settings)?;
+        check_for_new_and_old_process_data(sys, process_cache_data, settings, stats_cache.processes.as_ref())?;
     }
 
     debug!("Refreshed app/os usage data in {:?}", start.elapsed());
@@ -201,6 +584,22 @@ fn collect_and_save_data(
             SimpleDataCollectionMode::MEMORY_USED => convert_into_string_megabytes(sys.used_memory()),
             SimpleDataCollectionMode::MEMORY_AVAILABLE => convert_into_string_megabytes(sys.available_memory()),
             SimpleDataCollectionMode::MEMORY_FREE => convert_into_string_megabytes(sys.free_memory()),
+            SimpleDataCollectionMode::MEMORY_CACHED => format_meminfo(stats_cache, "Cached"),
+            SimpleDataCollectionMode::MEMORY_BUFFERS => format_meminfo(stats_cache, "Buffers"),
+            SimpleDataCollectionMode::MEMORY_SHMEM => format_meminfo(stats_cache, "Shmem"),
+            SimpleDataCollectionMode::MEMORY_DIRTY => format_meminfo(stats_cache, "Dirty"),
+            SimpleDataCollectionMode::MEMORY_WRITEBACK => format_meminfo(stats_cache, "Writeback"),
+            SimpleDataCollectionMode::MEMORY_SLAB_RECLAIMABLE => format_meminfo(stats_cache, "SReclaimable"),
+            SimpleDataCollectionMode::MEMORY_SLAB_UNRECLAIMABLE => format_meminfo(stats_cache, "SUnreclaim"),
+            SimpleDataCollectionMode::MEMORY_ANON => format_meminfo(stats_cache, "AnonPages"),
+            SimpleDataCollectionMode::PAGE_FAULTS_MINOR => format_vmstat_rates(stats_cache, |e| e.minor_page_faults_per_second),
+            SimpleDataCollectionMode::PAGE_FAULTS_MAJOR => format_vmstat_rates(stats_cache, |e| e.major_page_faults_per_second),
+            SimpleDataCollectionMode::PAGE_SCAN => format_vmstat_rates(stats_cache, |e| e.page_scan_per_second),
+            SimpleDataCollectionMode::PAGE_STEAL => format_vmstat_rates(stats_cache, |e| e.page_steal_per_second),
+            SimpleDataCollectionMode::OOM_KILLS => match stats_cache.vmstat.as_ref().and_then(|e| e.oom_kills) {
+                Some(oom_kills) => oom_kills.to_string(),
+                None => "-1".to_string(),
+            },
             SimpleDataCollectionMode::CPU_USAGE_TOTAL => {
                 format!(
                     "{:.2}",
@@ -208,26 +607,166 @@ fn collect_and_save_data(
                 )
             }
             SimpleDataCollectionMode::CPU_USAGE_PER_CORE => sys.cpus().iter().map(|e| format!("{:.2}", e.cpu_usage())).collect::<Vec<_>>().join(";"),
+            SimpleDataCollectionMode::CPU_FREQUENCY_PER_CORE => match &stats_cache.cpu_frequency {
+                Some(cpu_frequency) => cpu_frequency
+                    .frequencies_mhz
+                    .iter()
+                    .map(|e| format_optional_value(*e))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+                None => String::new(),
+            },
+            SimpleDataCollectionMode::CPU_USER => format_cpu_states(stats_cache, |e| e.user),
+            SimpleDataCollectionMode::CPU_NICE => format_cpu_states(stats_cache, |e| e.nice),
+            SimpleDataCollectionMode::CPU_SYSTEM => format_cpu_states(stats_cache, |e| e.system),
+            SimpleDataCollectionMode::CPU_IDLE => format_cpu_states(stats_cache, |e| e.idle),
+            SimpleDataCollectionMode::CPU_IOWAIT => format_cpu_states(stats_cache, |e| e.iowait),
+            SimpleDataCollectionMode::CPU_IRQ => format_cpu_states(stats_cache, |e| e.irq),
+            SimpleDataCollectionMode::CPU_SOFTIRQ => format_cpu_states(stats_cache, |e| e.softirq),
+            SimpleDataCollectionMode::CPU_STEAL => format_cpu_states(stats_cache, |e| e.steal),
+            SimpleDataCollectionMode::CPU_USER_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.user),
+            SimpleDataCollectionMode::CPU_NICE_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.nice),
+            SimpleDataCollectionMode::CPU_SYSTEM_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.system),
+            SimpleDataCollectionMode::CPU_IDLE_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.idle),
+            SimpleDataCollectionMode::CPU_IOWAIT_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.iowait),
+            SimpleDataCollectionMode::CPU_IRQ_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.irq),
+            SimpleDataCollectionMode::CPU_SOFTIRQ_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.softirq),
+            SimpleDataCollectionMode::CPU_STEAL_PER_CORE => format_cpu_states_per_core(stats_cache, |e| e.steal),
             SimpleDataCollectionMode::SWAP_FREE => convert_into_string_megabytes(sys.free_swap()),
             SimpleDataCollectionMode::SWAP_USED => convert_into_string_megabytes(sys.used_swap()),
+            SimpleDataCollectionMode::SWAP_IN => format_vmstat_rates(stats_cache, |e| e.swap_in_pages_per_second),
+            SimpleDataCollectionMode::SWAP_OUT => format_vmstat_rates(stats_cache, |e| e.swap_out_pages_per_second),
+            SimpleDataCollectionMode::DISK_READ_SPEED => format_disk_rates(stats_cache, |e| e.read_bytes_per_second / 1024.0 / 1024.0),
+            SimpleDataCollectionMode::DISK_WRITE_SPEED => format_disk_rates(stats_cache, |e| e.write_bytes_per_second / 1024.0 / 1024.0),
+            SimpleDataCollectionMode::DISK_READ_IOPS => format_disk_rates(stats_cache, |e| e.read_iops),
+            SimpleDataCollectionMode::DISK_WRITE_IOPS => format_disk_rates(stats_cache, |e| e.write_iops),
+            SimpleDataCollectionMode::DISK_BUSY => format_disk_rates(stats_cache, |e| e.busy_percent),
+            SimpleDataCollectionMode::NETWORK_RX_SPEED => format_network_rates(stats_cache, |e| e.rx_bytes_per_second / 1024.0 / 1024.0),
+            SimpleDataCollectionMode::NETWORK_TX_SPEED => format_network_rates(stats_cache, |e| e.tx_bytes_per_second / 1024.0 / 1024.0),
+            SimpleDataCollectionMode::NETWORK_RX_PACKETS => format_network_rates(stats_cache, |e| e.rx_packets_per_second),
+            SimpleDataCollectionMode::NETWORK_TX_PACKETS => format_network_rates(stats_cache, |e| e.tx_packets_per_second),
+            SimpleDataCollectionMode::NETWORK_RX_ERRORS => format_network_rates(stats_cache, |e| e.rx_errors_per_second),
+            SimpleDataCollectionMode::NETWORK_TX_ERRORS => format_network_rates(stats_cache, |e| e.tx_errors_per_second),
+            SimpleDataCollectionMode::NETWORK_RX_DROPPED => format_network_rates(stats_cache, |e| e.rx_dropped_per_second),
+            SimpleDataCollectionMode::NETWORK_TX_DROPPED => format_network_rates(stats_cache, |e| e.tx_dropped_per_second),
+            SimpleDataCollectionMode::CONTEXT_SWITCHES => format_kernel_activity(stats_cache, |e| e.context_switches_per_second),
+            SimpleDataCollectionMode::INTERRUPTS => format_kernel_activity(stats_cache, |e| e.interrupts_per_second),
+            SimpleDataCollectionMode::FORKS => format_kernel_activity(stats_cache, |e| e.forks_per_second),
+            SimpleDataCollectionMode::PROCESSES_RUNNING => format_kernel_activity(stats_cache, |e| e.processes_running),
+            SimpleDataCollectionMode::PROCESSES_BLOCKED => format_kernel_activity(stats_cache, |e| e.processes_blocked),
+            SimpleDataCollectionMode::TASKS_PROCESSES => format_tasks(stats_cache, |e| e.processes),
+            SimpleDataCollectionMode::TASKS_THREADS => format_tasks(stats_cache, |e| e.threads),
+            SimpleDataCollectionMode::TASKS_RUNNING => format_tasks(stats_cache, |e| e.running),
+            SimpleDataCollectionMode::TASKS_SLEEPING => format_tasks(stats_cache, |e| e.sleeping),
+            SimpleDataCollectionMode::TASKS_DISK_SLEEP => format_tasks(stats_cache, |e| e.disk_sleep),
+            SimpleDataCollectionMode::TASKS_ZOMBIE => format_tasks(stats_cache, |e| e.zombie),
+            SimpleDataCollectionMode::OPEN_FILES => format_descriptors(stats_cache, |e| e.open_files),
+            SimpleDataCollectionMode::OPEN_FILES_LIMIT => format_descriptors(stats_cache, |e| e.open_files_limit),
+            SimpleDataCollectionMode::SOCKETS_USED => format_descriptors(stats_cache, |e| e.sockets_used),
+            SimpleDataCollectionMode::SOCKETS_TCP_IN_USE => format_descriptors(stats_cache, |e| e.tcp_in_use),
+            SimpleDataCollectionMode::SOCKETS_TCP_ORPHAN => format_descriptors(stats_cache, |e| e.tcp_orphan),
+            SimpleDataCollectionMode::SOCKETS_TCP_TIME_WAIT => format_descriptors(stats_cache, |e| e.tcp_time_wait),
+            SimpleDataCollectionMode::SOCKETS_TCP_MEMORY => format_descriptors_megabytes(stats_cache, |e| e.tcp_memory_bytes),
+            SimpleDataCollectionMode::SOCKETS_UDP_IN_USE => format_descriptors(stats_cache, |e| e.udp_in_use),
+            SimpleDataCollectionMode::SOCKETS_UDP_MEMORY => format_descriptors_megabytes(stats_cache, |e| e.udp_memory_bytes),
+            SimpleDataCollectionMode::USER_CPU => format_users(stats_cache, |e| format!("{:.2}", e.cpu_usage_percent)),
+            SimpleDataCollectionMode::USER_MEMORY => format_users(stats_cache, |e| convert_into_string_megabytes(e.memory_bytes)),
+            SimpleDataCollectionMode::TCP_ESTABLISHED => format_tcp_states(stats_cache, |e| e.established),
+            SimpleDataCollectionMode::TCP_SYN_SENT => format_tcp_states(stats_cache, |e| e.syn_sent),
+            SimpleDataCollectionMode::TCP_SYN_RECV => format_tcp_states(stats_cache, |e| e.syn_recv),
+            SimpleDataCollectionMode::TCP_FIN_WAIT1 => format_tcp_states(stats_cache, |e| e.fin_wait1),
+            SimpleDataCollectionMode::TCP_FIN_WAIT2 => format_tcp_states(stats_cache, |e| e.fin_wait2),
+            SimpleDataCollectionMode::TCP_TIME_WAIT => format_tcp_states(stats_cache, |e| e.time_wait),
+            SimpleDataCollectionMode::TCP_CLOSE => format_tcp_states(stats_cache, |e| e.close),
+            SimpleDataCollectionMode::TCP_CLOSE_WAIT => format_tcp_states(stats_cache, |e| e.close_wait),
+            SimpleDataCollectionMode::TCP_LAST_ACK => format_tcp_states(stats_cache, |e| e.last_ack),
+            SimpleDataCollectionMode::TCP_LISTEN => format_tcp_states(stats_cache, |e| e.listen),
+            SimpleDataCollectionMode::TCP_CLOSING => format_tcp_states(stats_cache, |e| e.closing),
+            SimpleDataCollectionMode::TEMPERATURE => match &stats_cache.temperature {
+                Some(temperature) => temperature
+                    .temperatures
+                    .iter()
+                    .map(|e| format_optional_value(*e))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+                None => String::new(),
+            },
+            SimpleDataCollectionMode::POWER_CAPACITY => format_power_supply(stats_cache, |e| e.capacity_percent),
+            SimpleDataCollectionMode::POWER_STATUS => format_power_supply(stats_cache, |e| e.status),
+            SimpleDataCollectionMode::POWER_VOLTAGE => format_power_supply(stats_cache, |e| e.voltage),
+            SimpleDataCollectionMode::POWER_CURRENT => format_power_supply(stats_cache, |e| e.current),
+            SimpleDataCollectionMode::POWER_USAGE => format_power_supply(stats_cache, |e| e.power),
+            SimpleDataCollectionMode::LOAD_AVERAGE_1 => format!("{:.2}", System::load_average().one),
+            SimpleDataCollectionMode::LOAD_AVERAGE_5 => format!("{:.2}", System::load_average().five),
+            SimpleDataCollectionMode::LOAD_AVERAGE_15 => format!("{:.2}", System::load_average().fifteen),
+            SimpleDataCollectionMode::PRESSURE_CPU_SOME => format_pressure(stats_cache, |e| e.cpu.some_avg10),
+            SimpleDataCollectionMode::PRESSURE_CPU_FULL => format_pressure(stats_cache, |e| e.cpu.full_avg10),
+            SimpleDataCollectionMode::PRESSURE_MEMORY_SOME => format_pressure(stats_cache, |e| e.memory.some_avg10),
+            SimpleDataCollectionMode::PRESSURE_MEMORY_FULL => format_pressure(stats_cache, |e| e.memory.full_avg10),
+            SimpleDataCollectionMode::PRESSURE_IO_SOME => format_pressure(stats_cache, |e| e.io.some_avg10),
+            SimpleDataCollectionMode::PRESSURE_IO_FULL => format_pressure(stats_cache, |e| e.io.full_avg10),
         };
         data_to_save.push(collected_string);
     }
     if settings.need_to_refresh_processes {
-        for process_opt in &process_cache_data.process_used {
+        for (idx, process_opt) in process_cache_data.process_used.iter().enumerate() {
+            if settings.process_cmd_to_search[idx].all_matching {
+                data_to_save.extend(format_all_processes(&process_cache_data.all_processes_used[idx], sys, settings));
+                continue;
+            }
             if let Some(process) = process_opt {
-                data_to_save.push(format!("{:.2}", process.cpu_usage / sys.cpus().len() as f32));
+                data_to_save.push(format_optional_value(process.cpu_usage.map(|e| f64::from(e / sys.cpus().len() as f32))));
                 data_to_save.push(convert_into_string_megabytes(process.memory_usage));
             } else {
                 data_to_save.push("-1".to_string());
                 data_to_save.push("-1".to_string());
             }
+            if settings.extended_process_metrics {
+                data_to_save.extend(format_process_details(process_opt.as_ref().map(|e| &e.details.details)));
+            }
+            if settings.process_pss_memory {
+                data_to_save.extend(format_smaps_rollup(process_opt.as_ref().and_then(|e| e.smaps_rollup.as_ref())));
+            }
+        }
+    }
+    if let Some(disk_space) = &stats_cache.disk_space {
+        for disk_space_opt in &disk_space.disk_spaces {
+            if let Some(disk_space) = disk_space_opt {
+                data_to_save.push(convert_into_string_megabytes(disk_space.used_bytes));
+                data_to_save.push(convert_into_string_megabytes(disk_space.available_bytes));
+                data_to_save.push(format!("{:.2}", disk_space.inodes_used_percent));
+            } else {
+                data_to_save.push("-1".to_string());
+                data_to_save.push("-1".to_string());
+                data_to_save.push("-1".to_string());
+            }
+        }
+    }
+    if let Some(cgroup) = &stats_cache.cgroup {
+        for cgroup_values in &cgroup.values {
+            data_to_save.extend(format_cgroup_values(cgroup_values.as_ref()));
         }
     }
 
     let data_to_save_str = data_to_save.join(",");
     *collected_bytes += data_to_save_str.len();
 
+    // Rows are linked with data file by the same SECONDS_SINCE_START value
+    let seconds_since_start = &data_to_save[0];
+    let top_processes_lines = match stats_cache.top_processes.as_ref().and_then(|e| e.snapshot.as_ref()) {
+        Some(snapshot) => vec![format!("{seconds_since_start},{}", format_top_processes(snapshot))],
+        None => Vec::new(),
+    };
+    let process_events_lines = match &stats_cache.process_events {
+        Some(process_events) => process_events
+            .events
+            .iter()
+            .map(|e| format!("{seconds_since_start},{}", format_process_event(e)))
+            .collect(),
+        None => Vec::new(),
+    };
+    *collected_bytes += top_processes_lines.iter().chain(&process_events_lines).map(String::len).sum::<usize>();
+
     if *collected_bytes >= settings.maximum_data_file_size_bytes {
         let _ = data_file.flush();
         return Err(Error::msg(format!(
@@ -242,9 +781,202 @@ fn collect_and_save_data(
         data_file.flush().context(format!("Failed to flush data file {}", settings.data_path))?;
     }
 
+    if let Some(top_processes_file) = &mut sidecar_files.top_processes {
+        top_processes_file.write_lines(&top_processes_lines, settings)?;
+    }
+    if let Some(process_events_file) = &mut sidecar_files.process_events {
+        process_events_file.write_lines(&process_events_lines, settings)?;
+    }
+
     Ok(())
 }
 
+// Cmd is saved as last value, because it may contain ,
+fn format_process_event(event: &ProcessEvent) -> String {
+    format!(
+        "{},{},{},{},{},{}",
+        event.kind,
+        event.pid,
+        event.ppid,
+        event.name,
+        format_optional_value(event.lifetime_seconds),
+        event.cmd
+    )
+}
+
+// Processes are saved as pid:name:value split by ;, name cannot contain ; or , but may contain :
+fn format_top_processes(snapshot: &TopProcessesSnapshot) -> String {
+    let top_cpu = snapshot
+        .top_cpu
+        .iter()
+        .map(|e| format!("{}:{}:{:.2}", e.pid, e.name, e.cpu_usage_percent))
+        .collect::<Vec<_>>()
+        .join(";");
+    let top_memory = snapshot
+        .top_memory
+        .iter()
+        .map(|e| format!("{}:{}:{}", e.pid, e.name, convert_into_string_megabytes(e.memory_bytes)))
+        .collect::<Vec<_>>()
+        .join(";");
+    format!("{top_cpu},{top_memory}")
+}
+
+fn format_disk_rates(stats_cache: &SystemStatsCache, value: impl Fn(&DiskRates) -> f64) -> String {
+    let Some(disk) = &stats_cache.disk else {
+        return String::new();
+    };
+    format_per_item_values(&disk.rates, value)
+}
+
+fn format_network_rates(stats_cache: &SystemStatsCache, value: impl Fn(&NetworkRates) -> f64) -> String {
+    let Some(network) = &stats_cache.network else {
+        return String::new();
+    };
+    format_per_item_values(&network.rates, value)
+}
+
+fn format_cpu_states(stats_cache: &SystemStatsCache, value: impl Fn(&CpuStates) -> f64) -> String {
+    format_optional_value(stats_cache.proc_stat.as_ref().and_then(|e| e.total_states.as_ref()).map(value))
+}
+
+fn format_cpu_states_per_core(stats_cache: &SystemStatsCache, value: impl Fn(&CpuStates) -> f64) -> String {
+    let Some(proc_stat) = &stats_cache.proc_stat else {
+        return String::new();
+    };
+    format_per_item_values(&proc_stat.core_states, value)
+}
+
+fn format_kernel_activity(stats_cache: &SystemStatsCache, value: impl Fn(&KernelActivity) -> f64) -> String {
+    format_optional_value(stats_cache.proc_stat.as_ref().and_then(|e| e.kernel_activity.as_ref()).map(value))
+}
+
+fn format_meminfo(stats_cache: &SystemStatsCache, key: &str) -> String {
+    match stats_cache.meminfo.as_ref().and_then(|e| e.get(key)) {
+        Some(bytes) => convert_into_string_megabytes(bytes),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_vmstat_rates(stats_cache: &SystemStatsCache, value: impl Fn(&VmStatRates) -> f64) -> String {
+    format_optional_value(stats_cache.vmstat.as_ref().and_then(|e| e.rates.as_ref()).map(value))
+}
+
+fn format_tasks(stats_cache: &SystemStatsCache, value: impl Fn(&TasksStatsCache) -> u64) -> String {
+    match &stats_cache.tasks {
+        Some(tasks) => value(tasks).to_string(),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_descriptors(stats_cache: &SystemStatsCache, value: impl Fn(&DescriptorsStatsCache) -> Option<u64>) -> String {
+    match stats_cache.descriptors.as_ref().and_then(value) {
+        Some(value) => value.to_string(),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_descriptors_megabytes(stats_cache: &SystemStatsCache, value: impl Fn(&DescriptorsStatsCache) -> Option<u64>) -> String {
+    match stats_cache.descriptors.as_ref().and_then(value) {
+        Some(bytes) => convert_into_string_megabytes(bytes),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_users(stats_cache: &SystemStatsCache, value: impl Fn(&UserUsage) -> String) -> String {
+    let Some(users) = &stats_cache.users else {
+        return String::new();
+    };
+    users
+        .usages
+        .iter()
+        .map(|e| format!("{}:{}", e.name, value(e)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn format_tcp_states(stats_cache: &SystemStatsCache, value: impl Fn(&TcpStates) -> u64) -> String {
+    match stats_cache.tcp_states.as_ref().and_then(|e| e.states.as_ref()) {
+        Some(states) => value(states).to_string(),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_power_supply(stats_cache: &SystemStatsCache, value: impl Fn(&PowerSupplyValues) -> Option<f64>) -> String {
+    let Some(power_supply) = &stats_cache.power_supply else {
+        return String::new();
+    };
+    power_supply
+        .values
+        .iter()
+        .map(|e| format_optional_value(value(e)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn format_pressure(stats_cache: &SystemStatsCache, value: impl Fn(&PressureStatsCache) -> Option<f64>) -> String {
+    format_optional_value(stats_cache.pressure.as_ref().and_then(value))
+}
+
+fn format_process_details(details: Option<&ProcessDetails>) -> [String; 7] {
+    let Some(details) = details else {
+        return std::array::from_fn(|_| "-1".to_string());
+    };
+    [
+        format_optional_value(details.threads),
+        format_optional_value(details.open_files),
+        format_optional_value(details.virtual_memory_bytes.map(convert_bytes_into_mega_bytes)),
+        format_optional_value(details.voluntary_context_switches_per_second),
+        format_optional_value(details.involuntary_context_switches_per_second),
+        format_optional_value(details.read_bytes_per_second.map(|e| e / 1024.0 / 1024.0)),
+        format_optional_value(details.write_bytes_per_second.map(|e| e / 1024.0 / 1024.0)),
+    ]
+}
+
+fn format_smaps_rollup(smaps_rollup: Option<&SmapsRollup>) -> [String; 5] {
+    let Some(smaps_rollup) = smaps_rollup else {
+        return std::array::from_fn(|_| "-1".to_string());
+    };
+    [
+        smaps_rollup.pss_bytes,
+        smaps_rollup.pss_anon_bytes,
+        smaps_rollup.pss_file_bytes,
+        smaps_rollup.pss_shmem_bytes,
+        smaps_rollup.swap_pss_bytes,
+    ]
+    .map(|e| format_optional_value(e.map(convert_bytes_into_mega_bytes)))
+}
+
+// Memory limit is saved as -1 also when cgroup has no limit set
+fn format_cgroup_values(cgroup_values: Option<&CgroupValues>) -> [String; 6] {
+    let Some(cgroup_values) = cgroup_values else {
+        return std::array::from_fn(|_| "-1".to_string());
+    };
+    [
+        format_optional_value(cgroup_values.memory_current_bytes.map(convert_bytes_into_mega_bytes)),
+        format_optional_value(cgroup_values.memory_max_bytes.map(convert_bytes_into_mega_bytes)),
+        format_optional_value(cgroup_values.cpu_usage_percent),
+        format_optional_value(cgroup_values.throttled_periods_per_second),
+        format_optional_value(cgroup_values.io_read_bytes_per_second.map(|e| e / 1024.0 / 1024.0)),
+        format_optional_value(cgroup_values.io_write_bytes_per_second.map(|e| e / 1024.0 / 1024.0)),
+    ]
+}
+
+// Values which are not available are saved as -1, the same as not found processes
+fn format_optional_value(value: Option<f64>) -> String {
+    match value {
+        Some(value) => format!("{value:.2}"),
+        None => "-1".to_string(),
+    }
+}
+
+fn format_per_item_values<T>(items: &[Option<T>], value: impl Fn(&T) -> f64) -> String {
+    items
+        .iter()
+        .map(|e| format_optional_value(e.as_ref().map(&value)))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
 // Sys-info not have enough fast to check for available processes
 // In this step I don't need any info except running process pids
 pub fn get_system_pids() -> Result<HashSet<usize>, Error> {
@@ -272,18 +1004,38 @@ pub fn get_system_pids() -> Result<HashSet<usize>, Error> {
 // 1. Get all system pids
 // 2. Check for new processes and update them if are > 30, then use sys.refresh_processes_specific, to update them all in batch(probably cheaper than updating one by one)
 
-pub fn check_for_new_and_old_process_data(sys: &mut System, process_cache_data: &mut ProcessCache, settings: &Settings) -> Result<(), Error> {
-    let system_pids = get_system_pids()?;
+// Processes read in this tick are used when available, to not walk /proc again
+pub fn check_for_new_and_old_process_data(
+    sys: &mut System,
+    process_cache_data: &mut ProcessCache,
+    settings: &Settings,
+    processes: Option<&ProcessesStatsCache>,
+) -> Result<(), Error> {
+    let system_pids = match processes {
+        Some(processes) => processes.pids(),
+        None => get_system_pids()?,
+    };
 
     // If all searched processes are tracked, then app don't need to check for new processes
     // Only update used
     // This save a lot of processing time
-    if process_cache_data
+    // Searches of all matching processes need new check only when some process was started or one of them ended
+    let all_single_processes_tracked = process_cache_data
         .process_used
         .iter()
-        .all(|e| e.is_some() && system_pids.contains(&e.as_ref().unwrap().pid))
-    {
-        update_usage_of_tracked_process(process_cache_data, sys);
+        .zip(&settings.process_cmd_to_search)
+        .all(|(e, finding_struct)| finding_struct.all_matching || e.as_ref().is_some_and(|e| system_pids.contains(&e.pid)));
+    let all_matching_processes_alive = process_cache_data
+        .all_processes_used
+        .iter()
+        .flatten()
+        .all(|e| system_pids.contains(&e.pid));
+    let new_processes_started = settings.process_cmd_to_search.iter().any(|e| e.all_matching)
+        && system_pids
+            .iter()
+            .any(|pid| !process_cache_data.processes_checked_to_be_used.contains(pid));
+    if all_single_processes_tracked && all_matching_processes_alive && !new_processes_started {
+        update_usage_of_tracked_process(process_cache_data, sys, settings, processes);
         return Ok(());
     }
 
@@ -291,7 +1043,7 @@ pub fn check_for_new_and_old_process_data(sys: &mut System, process_cache_data:
     remove_tracking_of_removed_processes(process_cache_data, &system_pids);
     check_which_process_to_track(process_cache_data, sys, settings, &system_pids);
 
-    update_usage_of_tracked_process(process_cache_data, sys);
+    update_usage_of_tracked_process(process_cache_data, sys, settings, processes);
 
     process_cache_data.replace_checked_to_be_used_processes(system_pids.iter());
 
@@ -300,6 +1052,10 @@ pub fn check_for_new_and_old_process_data(sys: &mut System, process_cache_data:
 
 fn check_which_process_to_track(process_cache_data: &mut ProcessCache, sys: &mut System, settings: &Settings, system_pids: &HashSet<usize>) {
     for (idx, i) in settings.process_cmd_to_search.iter().enumerate() {
+        if i.all_matching {
+            track_all_matching_processes(process_cache_data, sys, settings, idx, system_pids);
+            continue;
+        }
         if process_cache_data.process_used[idx].is_some() {
             // Already monitoring process from such name
             continue;
@@ -313,8 +1069,18 @@ fn check_which_process_to_track(process_cache_data: &mut ProcessCache, sys: &mut
             if process_cache_data.processes_checked_to_be_used.contains(&pid_number) || !system_pids.contains(&pid_number) {
                 continue;
             }
-            let collected_name = process.cmd().iter().map(|e| e.to_string_lossy()).collect::<Vec<_>>().join(" ");
-            if collected_name.contains(&i.search_text) && collected_name.len() < shortest_text {
+            let collected_name = get_process_cmd(process);
+            // When tracking whole process tree, root of tree is needed, not one of its children with similar command
+            if i.include_children
+                && process.parent().is_some_and(|parent_pid| {
+                    sys.processes()
+                        .get(&parent_pid)
+                        .is_some_and(|parent| is_process_selected(parent_pid.into(), parent, &get_process_cmd(parent), i, settings))
+                })
+            {
+                continue;
+            }
+            if collected_name.len() < shortest_text && is_process_selected(pid_number, process, &collected_name, i, settings) {
                 shortest_text = collected_name.len();
                 shortest_matching_process_data = Some((pid_number, process, collected_name));
             }
@@ -333,6 +1099,35 @@ fn check_which_process_to_track(process_cache_data: &mut ProcessCache, sys: &mut
     }
 }
 
+// Only processes not checked in previous ticks are verified, so already tracked ones are not added twice
+fn track_all_matching_processes(process_cache_data: &mut ProcessCache, sys: &System, settings: &Settings, idx: usize, system_pids: &HashSet<usize>) {
+    let finding_struct = &settings.process_cmd_to_search[idx];
+    for (pid, process) in sys.processes() {
+        let pid_number: usize = (*pid).into();
+        if process_cache_data.processes_checked_to_be_used.contains(&pid_number) || !system_pids.contains(&pid_number) {
+            continue;
+        }
+        let collected_name = get_process_cmd(process);
+        if !is_process_selected(pid_number, process, &collected_name, finding_struct, settings) {
+            continue;
+        }
+        info!(
+            "Found process \"{}\" with pid \"{}\" that will be monitored as one of \"{}\" processes - (\"{}\")",
+            process.name().to_string_lossy(),
+            pid_number,
+            finding_struct.graph_name,
+            collected_name,
+        );
+        process_cache_data.processes_checked_to_be_used.insert(pid_number);
+        process_cache_data.all_processes_used[idx].push(CustomProcessData::from_process(process));
+    }
+    process_cache_data.all_processes_used[idx].sort_by_key(|e| e.pid);
+}
+
+fn is_process_selected(pid: usize, process: &Process, cmd: &str, finding_struct: &FindingStruct, settings: &Settings) -> bool {
+    finding_struct.selector.matches(pid, process, cmd) && !settings.process_exclusions.iter().any(|e| e.matches(pid, process, cmd))
+}
+
 fn remove_tracking_of_removed_processes(process_cache_data: &mut ProcessCache, system_pids: &HashSet<usize>) {
     process_cache_data.process_used = process_cache_data
         .process_used
@@ -354,9 +1149,23 @@ fn remove_tracking_of_removed_processes(process_cache_data: &mut ProcessCache, s
             }
         })
         .collect();
+
+    for processes in &mut process_cache_data.all_processes_used {
+        processes.retain(|e| {
+            let exists = system_pids.contains(&e.pid);
+            if !exists {
+                info!(
+                    "Process \"{}\" with pid \"{}\" is no longer available, removing from monitoring - (\"{}\")",
+                    e.name, e.pid, e.cmd_string
+                );
+            }
+            exists
+        });
+    }
 }
 
 // Needed to get processes name and cmd, rest is updated in update_usage_of_tracked_process
+// Cmd, exe, cwd and user are read only once, because they are needed only to find processes to track
 fn update_new_processes_stats(process_cache_data: &mut ProcessCache, sys: &mut System, system_pids: &HashSet<usize>) {
     let new_processes = process_cache_data.get_differences_in_usage_processes(system_pids.iter());
 
@@ -365,56 +1174,113 @@ fn update_new_processes_stats(process_cache_data: &mut ProcessCache, sys: &mut S
         sys.refresh_processes_specifics(
             ProcessesToUpdate::Some(&[Pid::from(new_processes[0])]),
             true,
-            ProcessRefreshKind::new().with_cpu(),
+            ProcessRefreshKind::new()
+                .with_cpu()
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_cwd(UpdateKind::OnlyIfNotSet)
+                .with_user(UpdateKind::OnlyIfNotSet),
         );
     } else if new_processes.len() > 1 {
         info!("Found {} new processes, refreshing them one by one", new_processes.len());
         sys.refresh_processes_specifics(
             ProcessesToUpdate::Some(&new_processes.iter().map(|e| Pid::from(*e)).collect::<Vec<_>>()),
             true,
-            ProcessRefreshKind::new().with_cpu(),
+            ProcessRefreshKind::new()
+                .with_cpu()
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_cwd(UpdateKind::OnlyIfNotSet)
+                .with_user(UpdateKind::OnlyIfNotSet),
         );
     }
 
     process_cache_data.replace_checked_usage_processes(system_pids.iter());
 }
 
-fn update_usage_of_tracked_process(process_cache_data: &mut ProcessCache, sys: &mut System) {
-    let process_count = process_cache_data.process_used.iter().flatten().count();
+fn update_usage_of_tracked_process(
+    process_cache_data: &mut ProcessCache,
+    sys: &mut System,
+    settings: &Settings,
+    processes: Option<&ProcessesStatsCache>,
+) {
+    let process_count = process_cache_data.process_used.iter().flatten().count() + process_cache_data.all_processes_used.iter().flatten().count();
     if process_count == 0 {
         return;
     }
-    debug!("Updating data of {process_count} processes");
 
-    let refresh_kind = ProcessRefreshKind::new().with_cpu();
-    if process_count > 1 {
-        sys.refresh_processes_specifics(
-            ProcessesToUpdate::Some(
-                &process_cache_data
-                    .process_used
-                    .iter()
-                    .flatten()
-                    .map(|e| Pid::from(e.pid))
-                    .collect::<Vec<_>>(),
-            ),
-            true,
-            refresh_kind,
-        );
-    } else {
-        sys.refresh_processes_specifics(
-            ProcessesToUpdate::Some(&[Pid::from(process_cache_data.process_used.iter().flatten().next().unwrap().pid)]),
-            true,
-            refresh_kind,
-        );
-    }
+    // Descendants are searched every tick, because short living children may appear and disappear at any moment
+    let children = match processes {
+        Some(processes) if settings.process_cmd_to_search.iter().any(|e| e.include_children) => processes.children(),
+        _ => HashMap::new(),
+    };
+    let descendants = process_cache_data
+        .process_used
+        .iter()
+        .zip(&settings.process_cmd_to_search)
+        .map(|(process_opt, finding_struct)| match process_opt {
+            Some(process) if finding_struct.include_children => find_descendants(process.pid, &children),
+            _ => Vec::new(),
+        })
+        .collect::<Vec<_>>();
 
-    for custom_process in process_cache_data.process_used.iter_mut().flatten() {
-        let Some(process) = sys.processes().get(&Pid::from(custom_process.pid)) else {
-            continue; // Process was removed since we last checked
+    // Same process may be tracked and also be descendant of other tracked process
+    // sysinfo removes processes which are passed more than once, so duplicates must be removed
+    let pids_to_refresh = process_cache_data
+        .process_used
+        .iter()
+        .flatten()
+        .chain(process_cache_data.all_processes_used.iter().flatten())
+        .map(|e| e.pid)
+        .chain(descendants.iter().flatten().copied())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(Pid::from)
+        .collect::<Vec<_>>();
+    debug!("Updating data of {} tracked processes and their descendants", pids_to_refresh.len());
+
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&pids_to_refresh), true, ProcessRefreshKind::new().with_memory());
+
+    for (custom_process, process_descendants) in process_cache_data.process_used.iter_mut().zip(&descendants) {
+        let Some(custom_process) = custom_process else {
+            continue;
         };
-        custom_process.memory_usage = process.memory();
-        custom_process.cpu_usage = process.cpu_usage();
+        if !update_process_usage(custom_process, sys, process_descendants) {
+            continue; // Process was removed since we last checked
+        }
+        if settings.extended_process_metrics {
+            custom_process.details.refresh(custom_process.pid);
+        }
+        if settings.process_pss_memory {
+            custom_process.smaps_rollup = read_smaps_rollup(custom_process.pid);
+        }
     }
+
+    for custom_process in process_cache_data.all_processes_used.iter_mut().flatten() {
+        update_process_usage(custom_process, sys, &[]);
+    }
+}
+
+// Returns false if process no longer exists
+// Cpu usage is read from /proc, because sysinfo computes it only when all processes are refreshed, which is too expensive
+// to do every tick, first sample of newly found process is unknown instead of 0
+fn update_process_usage(custom_process: &mut CustomProcessData, sys: &System, descendants: &[usize]) -> bool {
+    let Some(process) = sys.processes().get(&Pid::from(custom_process.pid)) else {
+        return false;
+    };
+    custom_process.memory_usage = process.memory()
+        + descendants
+            .iter()
+            .filter_map(|pid| sys.processes().get(&Pid::from(*pid)))
+            .map(Process::memory)
+            .sum::<u64>();
+    let tree_pids = [custom_process.pid].into_iter().chain(descendants.iter().copied()).collect::<Vec<_>>();
+    custom_process.cpu_usage = custom_process.cpu_tracker.refresh(&tree_pids);
+    true
+}
+
+fn get_process_cmd(process: &Process) -> String {
+    process.cmd().iter().map(|e| e.to_string_lossy()).collect::<Vec<_>>().join(" ")
 }
 
 // Only track changes > 10 KB
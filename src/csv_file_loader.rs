@@ -1,12 +1,14 @@
 use std::collections::HashMap;
+use std::fs;
 use std::fs::{metadata, File};
 use std::io::{BufRead, BufReader, Lines};
 
 use anyhow::{Context, Error, Result};
-use log::info;
+use log::{info, warn};
 
-use crate::enums::{DataType, GeneralInfoGroup, HeaderValues};
-use crate::model::{CollectedItemModels, Settings};
+use crate::collector::{get_process_events_path, get_top_processes_path, FOOTER_LINE_PREFIX};
+use crate::enums::{DataType, GeneralInfoGroup, HeaderValues, ProcessEventKind};
+use crate::model::{CollectedItemModels, ProcessEventEntry, Settings, TopProcessEntry, TopProcessesEntry};
 
 pub fn load_csv_results(settings: &Settings) -> Result<CollectedItemModels, Error> {
     info!(
@@ -24,34 +26,198 @@ pub fn load_csv_results(settings: &Settings) -> Result<CollectedItemModels, Erro
 
     let (swap_total, memory_total, cpu_core_count, check_interval, hashmap_data, start_time) = parse_file_values_data(&mut lines_iter)?;
     let (collected_data_names, collected_groups) = parse_header(&mut lines_iter, &hashmap_data)?;
-    let collected_data = parse_data(&mut lines_iter, &collected_data_names, cpu_core_count)?;
 
-    Ok(CollectedItemModels {
-        // collected_data_names,
-        collected_data,
+    let mut collected_items = CollectedItemModels {
+        collected_data_names: Vec::new(),
+        collected_data: HashMap::default(),
         collected_groups,
         memory_total,
         swap_total,
         cpu_core_count,
         check_interval,
         start_time,
-    })
+        disk_devices: parse_header_list(&hashmap_data, HeaderValues::DISK_DEVICES),
+        network_interfaces: parse_header_list(&hashmap_data, HeaderValues::NETWORK_INTERFACES),
+        temperature_sensors: parse_header_list(&hashmap_data, HeaderValues::TEMPERATURE_SENSORS),
+        power_supplies: parse_header_list(&hashmap_data, HeaderValues::POWER_SUPPLIES),
+        tcp_local_ports: parse_header_list(&hashmap_data, HeaderValues::TCP_LOCAL_PORTS),
+        effective_memory_limit: parse_optional_header_value(&hashmap_data, HeaderValues::EFFECTIVE_MEMORY_LIMIT)?,
+        effective_cpu_limit: parse_optional_header_value(&hashmap_data, HeaderValues::EFFECTIVE_CPU_LIMIT)?,
+        process_selectors: parse_process_selectors(&hashmap_data),
+        process_exclusions: parse_indexed_header_values(&hashmap_data, "PROCESS_EXCLUSION"),
+        top_processes: Vec::new(),
+        process_events: Vec::new(),
+        process_events_filters: parse_indexed_header_values(&hashmap_data, "PROCESS_EVENTS_FILTER"),
+        command: hashmap_data.get(&HeaderValues::COMMAND.to_string()).cloned(),
+        command_exit_status: None,
+        command_wall_time_seconds: None,
+    };
+    if hashmap_data.contains_key(&HeaderValues::TOP_PROCESSES.to_string()) {
+        collected_items.top_processes = load_top_processes(&get_top_processes_path(&settings.data_path));
+    }
+    if hashmap_data.contains_key(&HeaderValues::PROCESS_EVENTS.to_string()) {
+        collected_items.process_events = load_process_events(&get_process_events_path(&settings.data_path));
+    }
+    let (collected_data, footer_data) = parse_data(&mut lines_iter, &collected_data_names, &collected_items)?;
+    collected_items.collected_data = collected_data;
+    collected_items.collected_data_names = collected_data_names;
+    // Footer is missing when command was still running when collecting ended
+    collected_items.command_exit_status = footer_data.get(&HeaderValues::COMMAND_EXIT_STATUS.to_string()).cloned();
+    collected_items.command_wall_time_seconds = parse_optional_header_value(&footer_data, HeaderValues::COMMAND_WALL_TIME_SECONDS)?;
+
+    Ok(collected_items)
+}
+
+// Plot can be created without top processes, so missing or broken file is not treated as error
+fn load_top_processes(top_processes_path: &str) -> Vec<TopProcessesEntry> {
+    let content = match fs::read_to_string(top_processes_path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read top processes file {top_processes_path} - {e}");
+            return Vec::new();
+        }
+    };
+    parse_top_processes(&content)
+}
+
+fn parse_top_processes(content: &str) -> Vec<TopProcessesEntry> {
+    // First line contains only column names
+    let mut top_processes = Vec::new();
+    for line in content.lines().skip(1) {
+        let entry = line.split(',').collect::<Vec<_>>();
+        let [seconds_since_start, top_cpu, top_memory] = entry[..] else {
+            info!("Line \"{line}\" of top processes file is broken - not enough items, skipping it");
+            continue;
+        };
+        let Ok(seconds_since_start) = seconds_since_start.parse::<f64>() else {
+            info!("Line \"{line}\" of top processes file is broken - invalid time, skipping it");
+            continue;
+        };
+        top_processes.push(TopProcessesEntry {
+            seconds_since_start,
+            top_cpu: parse_top_process_entries(top_cpu),
+            top_memory: parse_top_process_entries(top_memory),
+        });
+    }
+    top_processes.sort_by(|a, b| a.seconds_since_start.total_cmp(&b.seconds_since_start));
+    top_processes
+}
+
+// Plot can be created without process events, so missing or broken file is not treated as error
+fn load_process_events(process_events_path: &str) -> Vec<ProcessEventEntry> {
+    let content = match fs::read_to_string(process_events_path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read process events file {process_events_path} - {e}");
+            return Vec::new();
+        }
+    };
+    parse_process_events(&content)
+}
+
+fn parse_process_events(content: &str) -> Vec<ProcessEventEntry> {
+    // First line contains only column names, cmd is last value and may contain ,
+    let mut process_events = Vec::new();
+    for line in content.lines().skip(1) {
+        let entry = line.splitn(7, ',').collect::<Vec<_>>();
+        let [seconds_since_start, kind, pid, ppid, name, lifetime_seconds, cmd] = entry[..] else {
+            info!("Line \"{line}\" of process events file is broken - not enough items, skipping it");
+            continue;
+        };
+        let (Ok(seconds_since_start), Ok(kind)) = (seconds_since_start.parse::<f64>(), kind.parse::<ProcessEventKind>()) else {
+            info!("Line \"{line}\" of process events file is broken - invalid time or event, skipping it");
+            continue;
+        };
+        process_events.push(ProcessEventEntry {
+            seconds_since_start,
+            kind,
+            pid: pid.to_string(),
+            ppid: ppid.to_string(),
+            name: name.to_string(),
+            lifetime_seconds: lifetime_seconds.to_string(),
+            cmd: cmd.to_string(),
+        });
+    }
+    process_events
+}
+
+// Items looks like - 1234:firefox:12.50, name may contain :
+fn parse_top_process_entries(items: &str) -> Vec<TopProcessEntry> {
+    items
+        .split(';')
+        .filter_map(|item| {
+            let (pid, rest) = item.split_once(':')?;
+            let (name, value) = rest.rsplit_once(':')?;
+            Some(TopProcessEntry {
+                pid: pid.to_string(),
+                name: name.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+// Lists are optional, because older files or files without certain data, don't contain them
+fn parse_header_list(hashmap_data: &HashMap<String, String>, header: HeaderValues) -> Vec<String> {
+    match hashmap_data.get(&header.to_string()) {
+        Some(value) if !value.is_empty() => value.split(';').map(ToString::to_string).collect(),
+        _ => Vec::new(),
+    }
 }
 
+// Older files don't contain selectors, because only search in run command was available
+fn parse_process_selectors(hashmap_data: &HashMap<String, String>) -> Vec<String> {
+    (0..)
+        .map_while(|idx| hashmap_data.get(&format!("CUSTOM_{idx}")).map(|name| (idx, name)))
+        .filter_map(|(idx, name)| {
+            hashmap_data
+                .get(&format!("CUSTOM_{idx}_SELECTOR"))
+                .map(|selector| format!("{name} - {selector}"))
+        })
+        .collect()
+}
+
+// Values saved as PREFIX_0=value,PREFIX_1=value...
+fn parse_indexed_header_values(hashmap_data: &HashMap<String, String>, prefix: &str) -> Vec<String> {
+    (0..).map_while(|idx| hashmap_data.get(&format!("{prefix}_{idx}")).cloned()).collect()
+}
+
+// Saved only when app was running with such limit
+fn parse_optional_header_value(hashmap_data: &HashMap<String, String>, header: HeaderValues) -> Result<Option<f64>, Error> {
+    match hashmap_data.get(&header.to_string()) {
+        Some(value) => Ok(Some(value.parse::<f64>().context(format!("Failed to parse {header} from general data"))?)),
+        None => Ok(None),
+    }
+}
+
+// Collected data and footer values
+type ParsedData = (HashMap<DataType, Vec<String>>, HashMap<String, String>);
+
 // TODO here should be added better error handling, if last line is broken, then this should ignore problem and continue
 // TODO consider to add debug check results type, may be available as option in settings
+// Returns also footer values, saved in the same format as in first line
 fn parse_data(
-    lines_iter: &mut Lines<BufReader<File>>,
+    lines_iter: &mut Lines<impl BufRead>,
     collected_data_names: &[DataType],
-    cpu_core_count: usize,
-) -> Result<HashMap<DataType, Vec<String>>, Error> {
+    collected_items: &CollectedItemModels,
+) -> Result<ParsedData, Error> {
     let mut collected_vec_data: Vec<Vec<String>> = Vec::new();
     for _ in 0..collected_data_names.len() {
         collected_vec_data.push(Vec::new());
     }
 
+    let mut footer_data = HashMap::new();
     for line in lines_iter {
         let line = line.context("Failed to read line of data file")?;
+        if let Some(footer) = line.strip_prefix(FOOTER_LINE_PREFIX) {
+            footer_data.extend(
+                footer
+                    .split(',')
+                    .filter_map(|item| item.split_once('='))
+                    .map(|(key, value)| (key.to_string(), value.to_string())),
+            );
+            continue;
+        }
         let mut split = line.split(',');
         if split.clone().count() != collected_data_names.len() {
             info!("Line \"{line}\" is broken - not enough items, skipping it");
@@ -64,40 +230,47 @@ fn parse_data(
     }
 
     let mut collected_data: HashMap<DataType, Vec<String>> = HashMap::default();
-    for (data_name, data) in collected_data_names.iter().zip(collected_vec_data.into_iter()) {
+    for (data_name, data) in collected_data_names.iter().zip(collected_vec_data) {
+        // Special formatting for per item data(like CPU usage per core) which is really Vec<Vec<String>> instead, Vec<String>
+        let data = if data_name.is_per_item() {
+            split_per_item_data(data_name, &data, collected_items.get_item_names(data_name).len())?
+        } else {
+            data
+        };
         collected_data.insert(data_name.clone(), data);
     }
 
-    // Special formatting for CPU usage per core which is really Vec<Vec<String>> instead, Vec<String>
-    if let Some(cpu_per_core_data) = collected_data.get(&DataType::CPU_USAGE_PER_CORE) {
-        let mut cpu_per_core_data_pre_formatted = Vec::new();
-        for _ in 0..cpu_core_count {
-            cpu_per_core_data_pre_formatted.push(Vec::new());
-        }
+    Ok((collected_data, footer_data))
+}
 
-        for cpu_core_data in cpu_per_core_data {
-            let mut split = cpu_core_data.split(';');
-            let count = split.clone().count();
-            if count != cpu_core_count {
-                return Err(Error::msg(format!(
-                    "Cpu data - \"{cpu_core_data}\" not contains required amount cpu usage results ({count}/{cpu_core_count})"
-                )));
-            }
-            for i in &mut cpu_per_core_data_pre_formatted {
-                // Unwrap is safe, because we checked this line earlier
-                i.push(split.next().unwrap().to_string());
-            }
-        }
+// Converts rows of items split by ; into columns of items split by ;
+fn split_per_item_data(data_name: &DataType, data: &[String], item_count: usize) -> Result<Vec<String>, Error> {
+    // e.g. no temperature sensors were found, so column contains only empty values
+    if item_count == 0 {
+        return Ok(Vec::new());
+    }
 
-        let mut cpu_per_core_data_formatted = Vec::new();
-        for data in cpu_per_core_data_pre_formatted {
-            cpu_per_core_data_formatted.push(data.join(";"));
-        }
+    let mut per_item_data_pre_formatted = Vec::new();
+    for _ in 0..item_count {
+        per_item_data_pre_formatted.push(Vec::new());
+    }
 
-        collected_data.insert(DataType::CPU_USAGE_PER_CORE, cpu_per_core_data_formatted);
+    for items_data in data {
+        let mut split = items_data.split(';');
+        let count = split.clone().count();
+        if count != item_count {
+            return Err(Error::msg(format!(
+                "{} data - \"{items_data}\" not contains required amount of results ({count}/{item_count})",
+                data_name.pretty_print()
+            )));
+        }
+        for i in &mut per_item_data_pre_formatted {
+            // Unwrap is safe, because we checked this line earlier
+            i.push(split.next().unwrap().to_string());
+        }
     }
 
-    Ok(collected_data)
+    Ok(per_item_data_pre_formatted.into_iter().map(|data| data.join(";")).collect())
 }
 
 fn parse_header(
@@ -115,25 +288,14 @@ fn parse_header(
             Ok(item) => Ok(item),
             Err(_) => {
                 if let Some(s) = item.strip_prefix("CUSTOM_") {
-                    let split = s.split('_').collect::<Vec<_>>();
-                    if split.len() != 2 || split[0].parse::<usize>().is_err() || !(matches!(split[1], "CPU" | "MEMORY")) {
-                        return Err(Error::msg(format!(
-                            "Failed to parse custom item {item}, should have format CUSTOM_{{IDX}}_CPU or CUSTOM_{{IDX}}_MEMORY"
-                        )));
-                    }
-                    let idx = split[0].parse::<usize>().unwrap();
-                    let name = hashmap_data
-                        .get(&format!("CUSTOM_{idx}"))
-                        .context(format!("Failed to find CUSTOM_{idx} in data file, but it is used in header"))?
-                        .to_string();
-                    if split[1] == "CPU" {
-                        Ok(DataType::CUSTOM_CPU((idx, name)))
-                    } else {
-                        Ok(DataType::CUSTOM_MEMORY((idx, name)))
-                    }
+                    parse_custom_item(item, s, hashmap_data)
+                } else if let Some(s) = item.strip_prefix("MOUNT_") {
+                    parse_mount_item(item, s, hashmap_data)
+                } else if let Some(s) = item.strip_prefix("CGROUP_") {
+                    parse_cgroup_item(item, s, hashmap_data)
                 } else {
                     Err(Error::msg(format!(
-                        "Failed to parse item {item} from data file, allowed values are {:?} or CUSTOM_ items",
+                        "Failed to parse item {item} from data file, allowed values are {:?} or CUSTOM_/MOUNT_/CGROUP_ items",
                         DataType::get_allowed_values()
                     )))
                 }
@@ -148,20 +310,111 @@ fn parse_header(
         return Err(Error::msg("First item in data file should be SECONDS_SINCE_START"));
     }
 
-    let mut collected_groups = Vec::new();
-    let cpu_collection = [DataType::CPU_USAGE_TOTAL, DataType::CPU_USAGE_PER_CORE];
-    let memory_collection = [DataType::MEMORY_AVAILABLE, DataType::MEMORY_FREE, DataType::MEMORY_USED];
-    let swap_collection = [DataType::SWAP_USED, DataType::SWAP_FREE];
-    if collected_data_names.iter().any(|e| cpu_collection.contains(e)) {
-        collected_groups.push(GeneralInfoGroup::CPU);
+    // Order of groups is also order of subplots
+    let collected_groups = [
+        GeneralInfoGroup::MEMORY,
+        GeneralInfoGroup::VIRTUAL_MEMORY,
+        GeneralInfoGroup::PROCESS_MEMORY,
+        GeneralInfoGroup::CPU,
+        GeneralInfoGroup::CPU_FREQUENCY,
+        GeneralInfoGroup::PRESSURE,
+        GeneralInfoGroup::KERNEL_ACTIVITY,
+        GeneralInfoGroup::TASKS,
+        GeneralInfoGroup::DESCRIPTORS,
+        GeneralInfoGroup::TCP_STATES,
+        GeneralInfoGroup::PROCESS_DETAILS,
+        GeneralInfoGroup::TEMPERATURE,
+        GeneralInfoGroup::POWER,
+        GeneralInfoGroup::SWAP,
+        GeneralInfoGroup::DISK,
+        GeneralInfoGroup::NETWORK,
+        GeneralInfoGroup::DISK_SPACE,
+        GeneralInfoGroup::CGROUP,
+    ]
+    .into_iter()
+    .filter(|group| collected_data_names.iter().any(|e| e.group() == Some(*group)))
+    .collect();
+
+    Ok((collected_data_names, collected_groups))
+}
+
+fn parse_custom_item(item: &str, s: &str, hashmap_data: &HashMap<String, String>) -> Result<DataType, Error> {
+    let Some((idx, kind)) = s.split_once('_') else {
+        return Err(Error::msg(format!(
+            "Failed to parse custom item {item}, should have format CUSTOM_{{IDX}}_CPU, CUSTOM_{{IDX}}_MEMORY or other CUSTOM_{{IDX}}_ item"
+        )));
+    };
+    let idx = idx.parse::<usize>().context(format!("Failed to parse index of custom item {item}"))?;
+    let name = hashmap_data
+        .get(&format!("CUSTOM_{idx}"))
+        .context(format!("Failed to find CUSTOM_{idx} in data file, but it is used in header"))?
+        .to_string();
+    match kind {
+        "CPU" => Ok(DataType::CUSTOM_CPU((idx, name))),
+        "MEMORY" => Ok(DataType::CUSTOM_MEMORY((idx, name))),
+        "CPU_PER_PID" => Ok(DataType::CUSTOM_CPU_PER_PID((idx, name))),
+        "MEMORY_PER_PID" => Ok(DataType::CUSTOM_MEMORY_PER_PID((idx, name))),
+        "THREADS" => Ok(DataType::CUSTOM_THREADS((idx, name))),
+        "OPEN_FILES" => Ok(DataType::CUSTOM_OPEN_FILES((idx, name))),
+        "VIRTUAL_MEMORY" => Ok(DataType::CUSTOM_VIRTUAL_MEMORY((idx, name))),
+        "VOLUNTARY_CONTEXT_SWITCHES" => Ok(DataType::CUSTOM_VOLUNTARY_CONTEXT_SWITCHES((idx, name))),
+        "INVOLUNTARY_CONTEXT_SWITCHES" => Ok(DataType::CUSTOM_INVOLUNTARY_CONTEXT_SWITCHES((idx, name))),
+        "DISK_READ" => Ok(DataType::CUSTOM_DISK_READ((idx, name))),
+        "DISK_WRITE" => Ok(DataType::CUSTOM_DISK_WRITE((idx, name))),
+        "PSS" => Ok(DataType::CUSTOM_PSS((idx, name))),
+        "PSS_ANON" => Ok(DataType::CUSTOM_PSS_ANON((idx, name))),
+        "PSS_FILE" => Ok(DataType::CUSTOM_PSS_FILE((idx, name))),
+        "PSS_SHMEM" => Ok(DataType::CUSTOM_PSS_SHMEM((idx, name))),
+        "SWAP_PSS" => Ok(DataType::CUSTOM_SWAP_PSS((idx, name))),
+        _ => Err(Error::msg(format!(
+            "Failed to parse custom item {item}, should have format CUSTOM_{{IDX}}_CPU, CUSTOM_{{IDX}}_MEMORY or other CUSTOM_{{IDX}}_ item"
+        ))),
     }
-    if collected_data_names.iter().any(|e| memory_collection.contains(e)) {
-        collected_groups.push(GeneralInfoGroup::MEMORY);
+}
+
+fn parse_mount_item(item: &str, s: &str, hashmap_data: &HashMap<String, String>) -> Result<DataType, Error> {
+    let Some((idx, kind)) = s.split_once('_') else {
+        return Err(Error::msg(format!(
+            "Failed to parse mount item {item}, should have format MOUNT_{{IDX}}_USED, MOUNT_{{IDX}}_AVAILABLE or MOUNT_{{IDX}}_INODES_USED"
+        )));
+    };
+    let idx = idx.parse::<usize>().context(format!("Failed to parse index of mount item {item}"))?;
+    let mount_point = hashmap_data
+        .get(&format!("MOUNT_{idx}"))
+        .context(format!("Failed to find MOUNT_{idx} in data file, but it is used in header"))?
+        .to_string();
+    match kind {
+        "USED" => Ok(DataType::MOUNT_USED((idx, mount_point))),
+        "AVAILABLE" => Ok(DataType::MOUNT_AVAILABLE((idx, mount_point))),
+        "INODES_USED" => Ok(DataType::MOUNT_INODES_USED((idx, mount_point))),
+        _ => Err(Error::msg(format!(
+            "Failed to parse mount item {item}, should have format MOUNT_{{IDX}}_USED, MOUNT_{{IDX}}_AVAILABLE or MOUNT_{{IDX}}_INODES_USED"
+        ))),
     }
-    if collected_data_names.iter().any(|e| swap_collection.contains(e)) {
-        collected_groups.push(GeneralInfoGroup::SWAP);
+}
+
+fn parse_cgroup_item(item: &str, s: &str, hashmap_data: &HashMap<String, String>) -> Result<DataType, Error> {
+    let Some((idx, kind)) = s.split_once('_') else {
+        return Err(Error::msg(format!(
+            "Failed to parse cgroup item {item}, should have format CGROUP_{{IDX}}_{{MEMORY|MEMORY_MAX|CPU|THROTTLED|IO_READ|IO_WRITE}}"
+        )));
+    };
+    let idx = idx.parse::<usize>().context(format!("Failed to parse index of cgroup item {item}"))?;
+    let name = hashmap_data
+        .get(&format!("CGROUP_{idx}"))
+        .context(format!("Failed to find CGROUP_{idx} in data file, but it is used in header"))?
+        .to_string();
+    match kind {
+        "MEMORY" => Ok(DataType::CGROUP_MEMORY((idx, name))),
+        "MEMORY_MAX" => Ok(DataType::CGROUP_MEMORY_MAX((idx, name))),
+        "CPU" => Ok(DataType::CGROUP_CPU((idx, name))),
+        "THROTTLED" => Ok(DataType::CGROUP_THROTTLED((idx, name))),
+        "IO_READ" => Ok(DataType::CGROUP_IO_READ((idx, name))),
+        "IO_WRITE" => Ok(DataType::CGROUP_IO_WRITE((idx, name))),
+        _ => Err(Error::msg(format!(
+            "Failed to parse cgroup item {item}, should have format CGROUP_{{IDX}}_{{MEMORY|MEMORY_MAX|CPU|THROTTLED|IO_READ|IO_WRITE}}"
+        ))),
     }
-    Ok((collected_data_names, collected_groups))
 }
 
 type ParsedOkResult = (f64, f64, usize, f32, HashMap<String, String>, f64);
@@ -209,3 +462,137 @@ fn parse_file_values_data(lines_iter: &mut Lines<BufReader<File>>) -> std::resul
 
     Ok((swap_total, memory_total, cpu_core_count, check_interval, general_data_hashmap, start_time))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_per_item_data() {
+        let data = vec!["1;10".to_string(), "2;20".to_string(), "3;-1".to_string()];
+        let split = split_per_item_data(&DataType::CPU_USAGE_PER_CORE, &data, 2).unwrap();
+        assert_eq!(split, vec!["1;2;3".to_string(), "10;20;-1".to_string()]);
+    }
+
+    #[test]
+    fn test_split_per_item_data_without_items() {
+        let data = vec![String::new(), String::new()];
+        assert!(split_per_item_data(&DataType::CPU_USAGE_PER_CORE, &data, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_per_item_data_with_wrong_item_count() {
+        let data = vec!["1;10".to_string(), "2;20;30".to_string()];
+        let error = split_per_item_data(&DataType::CPU_USAGE_PER_CORE, &data, 2).unwrap_err();
+        assert!(
+            error.to_string().contains("\"2;20;30\" not contains required amount of results (3/2)"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_parse_top_process_entries() {
+        let entries = parse_top_process_entries("1234:firefox:12.50;99:name:with:colons:3.00;42::0.10");
+        let entries = entries
+            .iter()
+            .map(|e| (e.pid.as_str(), e.name.as_str(), e.value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![("1234", "firefox", "12.50"), ("99", "name:with:colons", "3.00"), ("42", "", "0.10")]
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_top_process_entries() {
+        assert!(parse_top_process_entries("").is_empty());
+        let entries = parse_top_process_entries("1234;55:only_name;;7:bash:1.00");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "bash");
+    }
+
+    #[test]
+    fn test_parse_top_processes() {
+        let content = "SECONDS_SINCE_START,TOP_CPU,TOP_MEMORY
+2.00,1:init:5.00,1:init:1024
+1.00,2:bash:1.00;3:vim:0.50,
+broken line
+abc,1:init:5.00,1:init:1024
+3.00,1:init:5.00,1:init:1024,extra
+";
+        let top_processes = parse_top_processes(content);
+        assert_eq!(top_processes.iter().map(|e| e.seconds_since_start).collect::<Vec<_>>(), vec![1.0, 2.0]);
+        assert_eq!(top_processes[0].top_cpu.len(), 2);
+        assert!(top_processes[0].top_memory.is_empty());
+        assert_eq!(top_processes[1].top_memory[0].value, "1024");
+    }
+
+    #[test]
+    fn test_parse_process_events_with_commas_in_cmd() {
+        let content = "SECONDS_SINCE_START,EVENT,PID,PPID,NAME,LIFETIME_SECONDS,CMD
+1.50,STARTED,100,1,python3,-1,python3 -c print(1,2,3)
+2.50,EXITED,100,1,python3,1.00,python3 -c print(1,2,3)
+3.00,STARTED,101,1,sh,-1,
+";
+        let events = parse_process_events(content);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].kind, ProcessEventKind::STARTED);
+        assert_eq!(events[0].cmd, "python3 -c print(1,2,3)");
+        assert_eq!(events[1].kind, ProcessEventKind::EXITED);
+        assert_eq!((events[1].pid.as_str(), events[1].ppid.as_str()), ("100", "1"));
+        assert_eq!(events[1].lifetime_seconds, "1.00");
+        assert_eq!(events[2].cmd, "");
+    }
+
+    #[test]
+    fn test_parse_malformed_process_events() {
+        let content = "SECONDS_SINCE_START,EVENT,PID,PPID,NAME,LIFETIME_SECONDS,CMD
+1.00,STARTED,100,1,sh,-1
+abc,STARTED,100,1,sh,-1,sh
+1.00,RESTARTED,100,1,sh,-1,sh
+
+2.00,EXITED,100,1,sh,1.00,sh
+";
+        let events = parse_process_events(content);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].seconds_since_start, 2.0);
+    }
+
+    #[test]
+    fn test_missing_sidecar_files_are_empty() {
+        assert!(load_process_events("/non_existent_dir/system_data_process_events.csv").is_empty());
+        assert!(load_top_processes("/non_existent_dir/system_data_top_processes.csv").is_empty());
+    }
+
+    #[test]
+    fn test_parse_data_with_footer() {
+        let content = "0.00,10;20,100
+1.00,30;40
+1.00,11;21,200
+FOOTER,COMMAND_EXIT_STATUS=signal 9,COMMAND_WALL_TIME_SECONDS=1.25
+";
+        let collected_data_names = [DataType::SECONDS_SINCE_START, DataType::CPU_USAGE_PER_CORE, DataType::MEMORY_USED];
+        let collected_items = CollectedItemModels {
+            cpu_core_count: 2,
+            ..Default::default()
+        };
+        let (collected_data, footer_data) = parse_data(&mut content.as_bytes().lines(), &collected_data_names, &collected_items).unwrap();
+
+        assert_eq!(collected_data[&DataType::SECONDS_SINCE_START], vec!["0.00", "1.00"]);
+        assert_eq!(collected_data[&DataType::CPU_USAGE_PER_CORE], vec!["10;11", "20;21"]);
+        assert_eq!(collected_data[&DataType::MEMORY_USED], vec!["100", "200"]);
+        assert_eq!(footer_data["COMMAND_EXIT_STATUS"], "signal 9");
+        assert_eq!(footer_data["COMMAND_WALL_TIME_SECONDS"], "1.25");
+    }
+
+    #[test]
+    fn test_parse_data_without_footer_marker() {
+        // Line with = but without marker is not footer, so it is skipped as broken data line
+        let content = "0.00,100,50\nCOMMAND_EXIT_STATUS=0,COMMAND_WALL_TIME_SECONDS=1.00\n";
+        let collected_data_names = [DataType::SECONDS_SINCE_START, DataType::MEMORY_USED, DataType::MEMORY_FREE];
+        let (collected_data, footer_data) =
+            parse_data(&mut content.as_bytes().lines(), &collected_data_names, &CollectedItemModels::default()).unwrap();
+        assert_eq!(collected_data[&DataType::MEMORY_USED], vec!["100"]);
+        assert!(footer_data.is_empty());
+    }
+}
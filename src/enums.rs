@@ -10,12 +10,251 @@ pub enum SimpleDataCollectionMode {
     #[default]
     CPU_USAGE_TOTAL,
     CPU_USAGE_PER_CORE,
+    CPU_FREQUENCY_PER_CORE,
+    CPU_USER,
+    CPU_NICE,
+    CPU_SYSTEM,
+    CPU_IDLE,
+    CPU_IOWAIT,
+    CPU_IRQ,
+    CPU_SOFTIRQ,
+    CPU_STEAL,
+    CPU_USER_PER_CORE,
+    CPU_NICE_PER_CORE,
+    CPU_SYSTEM_PER_CORE,
+    CPU_IDLE_PER_CORE,
+    CPU_IOWAIT_PER_CORE,
+    CPU_IRQ_PER_CORE,
+    CPU_SOFTIRQ_PER_CORE,
+    CPU_STEAL_PER_CORE,
     SWAP_FREE,
     SWAP_USED,
+    SWAP_IN,
+    SWAP_OUT,
     MEMORY_USED,
     MEMORY_FREE,
     MEMORY_AVAILABLE,
-    // TODO CustomProcessName, CustomProcessId
+    MEMORY_CACHED,
+    MEMORY_BUFFERS,
+    MEMORY_SHMEM,
+    MEMORY_DIRTY,
+    MEMORY_WRITEBACK,
+    MEMORY_SLAB_RECLAIMABLE,
+    MEMORY_SLAB_UNRECLAIMABLE,
+    MEMORY_ANON,
+    PAGE_FAULTS_MINOR,
+    PAGE_FAULTS_MAJOR,
+    PAGE_SCAN,
+    PAGE_STEAL,
+    OOM_KILLS,
+    DISK_READ_SPEED,
+    DISK_WRITE_SPEED,
+    DISK_READ_IOPS,
+    DISK_WRITE_IOPS,
+    DISK_BUSY,
+    NETWORK_RX_SPEED,
+    NETWORK_TX_SPEED,
+    NETWORK_RX_PACKETS,
+    NETWORK_TX_PACKETS,
+    NETWORK_RX_ERRORS,
+    NETWORK_TX_ERRORS,
+    NETWORK_RX_DROPPED,
+    NETWORK_TX_DROPPED,
+    LOAD_AVERAGE_1,
+    LOAD_AVERAGE_5,
+    LOAD_AVERAGE_15,
+    PRESSURE_CPU_SOME,
+    PRESSURE_CPU_FULL,
+    PRESSURE_MEMORY_SOME,
+    PRESSURE_MEMORY_FULL,
+    PRESSURE_IO_SOME,
+    PRESSURE_IO_FULL,
+    CONTEXT_SWITCHES,
+    INTERRUPTS,
+    FORKS,
+    PROCESSES_RUNNING,
+    PROCESSES_BLOCKED,
+    TASKS_PROCESSES,
+    TASKS_THREADS,
+    TASKS_RUNNING,
+    TASKS_SLEEPING,
+    TASKS_DISK_SLEEP,
+    TASKS_ZOMBIE,
+    OPEN_FILES,
+    OPEN_FILES_LIMIT,
+    SOCKETS_USED,
+    SOCKETS_TCP_IN_USE,
+    SOCKETS_TCP_ORPHAN,
+    SOCKETS_TCP_TIME_WAIT,
+    SOCKETS_TCP_MEMORY,
+    SOCKETS_UDP_IN_USE,
+    SOCKETS_UDP_MEMORY,
+    TCP_ESTABLISHED,
+    TCP_SYN_SENT,
+    TCP_SYN_RECV,
+    TCP_FIN_WAIT1,
+    TCP_FIN_WAIT2,
+    TCP_TIME_WAIT,
+    TCP_CLOSE,
+    TCP_CLOSE_WAIT,
+    TCP_LAST_ACK,
+    TCP_LISTEN,
+    TCP_CLOSING,
+    TEMPERATURE,
+    POWER_CAPACITY,
+    POWER_STATUS,
+    POWER_VOLTAGE,
+    POWER_CURRENT,
+    POWER_USAGE,
+    USER_CPU,
+    USER_MEMORY,
+}
+
+impl SimpleDataCollectionMode {
+    pub fn is_meminfo(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::MEMORY_CACHED
+                | SimpleDataCollectionMode::MEMORY_BUFFERS
+                | SimpleDataCollectionMode::MEMORY_SHMEM
+                | SimpleDataCollectionMode::MEMORY_DIRTY
+                | SimpleDataCollectionMode::MEMORY_WRITEBACK
+                | SimpleDataCollectionMode::MEMORY_SLAB_RECLAIMABLE
+                | SimpleDataCollectionMode::MEMORY_SLAB_UNRECLAIMABLE
+                | SimpleDataCollectionMode::MEMORY_ANON
+        )
+    }
+    pub fn is_cpu_state(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::CPU_USER
+                | SimpleDataCollectionMode::CPU_NICE
+                | SimpleDataCollectionMode::CPU_SYSTEM
+                | SimpleDataCollectionMode::CPU_IDLE
+                | SimpleDataCollectionMode::CPU_IOWAIT
+                | SimpleDataCollectionMode::CPU_IRQ
+                | SimpleDataCollectionMode::CPU_SOFTIRQ
+                | SimpleDataCollectionMode::CPU_STEAL
+                | SimpleDataCollectionMode::CPU_USER_PER_CORE
+                | SimpleDataCollectionMode::CPU_NICE_PER_CORE
+                | SimpleDataCollectionMode::CPU_SYSTEM_PER_CORE
+                | SimpleDataCollectionMode::CPU_IDLE_PER_CORE
+                | SimpleDataCollectionMode::CPU_IOWAIT_PER_CORE
+                | SimpleDataCollectionMode::CPU_IRQ_PER_CORE
+                | SimpleDataCollectionMode::CPU_SOFTIRQ_PER_CORE
+                | SimpleDataCollectionMode::CPU_STEAL_PER_CORE
+        )
+    }
+    pub fn is_disk(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::DISK_READ_SPEED
+                | SimpleDataCollectionMode::DISK_WRITE_SPEED
+                | SimpleDataCollectionMode::DISK_READ_IOPS
+                | SimpleDataCollectionMode::DISK_WRITE_IOPS
+                | SimpleDataCollectionMode::DISK_BUSY
+        )
+    }
+    pub fn is_network(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::NETWORK_RX_SPEED
+                | SimpleDataCollectionMode::NETWORK_TX_SPEED
+                | SimpleDataCollectionMode::NETWORK_RX_PACKETS
+                | SimpleDataCollectionMode::NETWORK_TX_PACKETS
+                | SimpleDataCollectionMode::NETWORK_RX_ERRORS
+                | SimpleDataCollectionMode::NETWORK_TX_ERRORS
+                | SimpleDataCollectionMode::NETWORK_RX_DROPPED
+                | SimpleDataCollectionMode::NETWORK_TX_DROPPED
+        )
+    }
+    pub fn is_kernel_activity(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::CONTEXT_SWITCHES
+                | SimpleDataCollectionMode::INTERRUPTS
+                | SimpleDataCollectionMode::FORKS
+                | SimpleDataCollectionMode::PROCESSES_RUNNING
+                | SimpleDataCollectionMode::PROCESSES_BLOCKED
+        )
+    }
+    pub fn is_tasks(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::TASKS_PROCESSES
+                | SimpleDataCollectionMode::TASKS_THREADS
+                | SimpleDataCollectionMode::TASKS_RUNNING
+                | SimpleDataCollectionMode::TASKS_SLEEPING
+                | SimpleDataCollectionMode::TASKS_DISK_SLEEP
+                | SimpleDataCollectionMode::TASKS_ZOMBIE
+        )
+    }
+    pub fn is_descriptors(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::OPEN_FILES
+                | SimpleDataCollectionMode::OPEN_FILES_LIMIT
+                | SimpleDataCollectionMode::SOCKETS_USED
+                | SimpleDataCollectionMode::SOCKETS_TCP_IN_USE
+                | SimpleDataCollectionMode::SOCKETS_TCP_ORPHAN
+                | SimpleDataCollectionMode::SOCKETS_TCP_TIME_WAIT
+                | SimpleDataCollectionMode::SOCKETS_TCP_MEMORY
+                | SimpleDataCollectionMode::SOCKETS_UDP_IN_USE
+                | SimpleDataCollectionMode::SOCKETS_UDP_MEMORY
+        )
+    }
+    pub fn is_power(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::POWER_CAPACITY
+                | SimpleDataCollectionMode::POWER_STATUS
+                | SimpleDataCollectionMode::POWER_VOLTAGE
+                | SimpleDataCollectionMode::POWER_CURRENT
+                | SimpleDataCollectionMode::POWER_USAGE
+        )
+    }
+    pub fn is_users(&self) -> bool {
+        matches!(self, SimpleDataCollectionMode::USER_CPU | SimpleDataCollectionMode::USER_MEMORY)
+    }
+    pub fn is_vmstat(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::SWAP_IN
+                | SimpleDataCollectionMode::SWAP_OUT
+                | SimpleDataCollectionMode::PAGE_FAULTS_MINOR
+                | SimpleDataCollectionMode::PAGE_FAULTS_MAJOR
+                | SimpleDataCollectionMode::PAGE_SCAN
+                | SimpleDataCollectionMode::PAGE_STEAL
+                | SimpleDataCollectionMode::OOM_KILLS
+        )
+    }
+    pub fn is_tcp_states(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::TCP_ESTABLISHED
+                | SimpleDataCollectionMode::TCP_SYN_SENT
+                | SimpleDataCollectionMode::TCP_SYN_RECV
+                | SimpleDataCollectionMode::TCP_FIN_WAIT1
+                | SimpleDataCollectionMode::TCP_FIN_WAIT2
+                | SimpleDataCollectionMode::TCP_TIME_WAIT
+                | SimpleDataCollectionMode::TCP_CLOSE
+                | SimpleDataCollectionMode::TCP_CLOSE_WAIT
+                | SimpleDataCollectionMode::TCP_LAST_ACK
+                | SimpleDataCollectionMode::TCP_LISTEN
+                | SimpleDataCollectionMode::TCP_CLOSING
+        )
+    }
+    pub fn is_pressure(&self) -> bool {
+        matches!(
+            self,
+            SimpleDataCollectionMode::PRESSURE_CPU_SOME
+                | SimpleDataCollectionMode::PRESSURE_CPU_FULL
+                | SimpleDataCollectionMode::PRESSURE_MEMORY_SOME
+                | SimpleDataCollectionMode::PRESSURE_MEMORY_FULL
+                | SimpleDataCollectionMode::PRESSURE_IO_SOME
+                | SimpleDataCollectionMode::PRESSURE_IO_FULL
+        )
+    }
 }
 
 // Must contains same enums as above with additional SECONDS_SINCE_START and maybe some other
@@ -26,13 +265,129 @@ pub enum DataType {
     SECONDS_SINCE_START,
     CPU_USAGE_TOTAL,
     CPU_USAGE_PER_CORE,
+    CPU_FREQUENCY_PER_CORE,
+    CPU_USER,
+    CPU_NICE,
+    CPU_SYSTEM,
+    CPU_IDLE,
+    CPU_IOWAIT,
+    CPU_IRQ,
+    CPU_SOFTIRQ,
+    CPU_STEAL,
+    CPU_USER_PER_CORE,
+    CPU_NICE_PER_CORE,
+    CPU_SYSTEM_PER_CORE,
+    CPU_IDLE_PER_CORE,
+    CPU_IOWAIT_PER_CORE,
+    CPU_IRQ_PER_CORE,
+    CPU_SOFTIRQ_PER_CORE,
+    CPU_STEAL_PER_CORE,
     SWAP_FREE,
     SWAP_USED,
+    SWAP_IN,
+    SWAP_OUT,
     MEMORY_USED,
     MEMORY_FREE,
     MEMORY_AVAILABLE,
+    MEMORY_CACHED,
+    MEMORY_BUFFERS,
+    MEMORY_SHMEM,
+    MEMORY_DIRTY,
+    MEMORY_WRITEBACK,
+    MEMORY_SLAB_RECLAIMABLE,
+    MEMORY_SLAB_UNRECLAIMABLE,
+    MEMORY_ANON,
+    PAGE_FAULTS_MINOR,
+    PAGE_FAULTS_MAJOR,
+    PAGE_SCAN,
+    PAGE_STEAL,
+    OOM_KILLS,
+    DISK_READ_SPEED,
+    DISK_WRITE_SPEED,
+    DISK_READ_IOPS,
+    DISK_WRITE_IOPS,
+    DISK_BUSY,
+    NETWORK_RX_SPEED,
+    NETWORK_TX_SPEED,
+    NETWORK_RX_PACKETS,
+    NETWORK_TX_PACKETS,
+    NETWORK_RX_ERRORS,
+    NETWORK_TX_ERRORS,
+    NETWORK_RX_DROPPED,
+    NETWORK_TX_DROPPED,
+    LOAD_AVERAGE_1,
+    LOAD_AVERAGE_5,
+    LOAD_AVERAGE_15,
+    PRESSURE_CPU_SOME,
+    PRESSURE_CPU_FULL,
+    PRESSURE_MEMORY_SOME,
+    PRESSURE_MEMORY_FULL,
+    PRESSURE_IO_SOME,
+    PRESSURE_IO_FULL,
+    CONTEXT_SWITCHES,
+    INTERRUPTS,
+    FORKS,
+    PROCESSES_RUNNING,
+    PROCESSES_BLOCKED,
+    TASKS_PROCESSES,
+    TASKS_THREADS,
+    TASKS_RUNNING,
+    TASKS_SLEEPING,
+    TASKS_DISK_SLEEP,
+    TASKS_ZOMBIE,
+    OPEN_FILES,
+    OPEN_FILES_LIMIT,
+    SOCKETS_USED,
+    SOCKETS_TCP_IN_USE,
+    SOCKETS_TCP_ORPHAN,
+    SOCKETS_TCP_TIME_WAIT,
+    SOCKETS_TCP_MEMORY,
+    SOCKETS_UDP_IN_USE,
+    SOCKETS_UDP_MEMORY,
+    TCP_ESTABLISHED,
+    TCP_SYN_SENT,
+    TCP_SYN_RECV,
+    TCP_FIN_WAIT1,
+    TCP_FIN_WAIT2,
+    TCP_TIME_WAIT,
+    TCP_CLOSE,
+    TCP_CLOSE_WAIT,
+    TCP_LAST_ACK,
+    TCP_LISTEN,
+    TCP_CLOSING,
+    TEMPERATURE,
+    POWER_CAPACITY,
+    POWER_STATUS,
+    POWER_VOLTAGE,
+    POWER_CURRENT,
+    POWER_USAGE,
+    USER_CPU,
+    USER_MEMORY,
     CUSTOM_CPU((usize, String)),
     CUSTOM_MEMORY((usize, String)),
+    CUSTOM_CPU_PER_PID((usize, String)),
+    CUSTOM_MEMORY_PER_PID((usize, String)),
+    CUSTOM_THREADS((usize, String)),
+    CUSTOM_OPEN_FILES((usize, String)),
+    CUSTOM_VIRTUAL_MEMORY((usize, String)),
+    CUSTOM_VOLUNTARY_CONTEXT_SWITCHES((usize, String)),
+    CUSTOM_INVOLUNTARY_CONTEXT_SWITCHES((usize, String)),
+    CUSTOM_DISK_READ((usize, String)),
+    CUSTOM_DISK_WRITE((usize, String)),
+    CUSTOM_PSS((usize, String)),
+    CUSTOM_PSS_ANON((usize, String)),
+    CUSTOM_PSS_FILE((usize, String)),
+    CUSTOM_PSS_SHMEM((usize, String)),
+    CUSTOM_SWAP_PSS((usize, String)),
+    MOUNT_USED((usize, String)),
+    MOUNT_AVAILABLE((usize, String)),
+    MOUNT_INODES_USED((usize, String)),
+    CGROUP_MEMORY((usize, String)),
+    CGROUP_MEMORY_MAX((usize, String)),
+    CGROUP_CPU((usize, String)),
+    CGROUP_THROTTLED((usize, String)),
+    CGROUP_IO_READ((usize, String)),
+    CGROUP_IO_WRITE((usize, String)),
 }
 
 impl DataType {
@@ -43,27 +398,400 @@ impl DataType {
     pub fn is_memory(&self) -> bool {
         matches!(
             self,
-            DataType::MEMORY_USED | DataType::MEMORY_FREE | DataType::MEMORY_AVAILABLE | DataType::CUSTOM_MEMORY(_)
+            DataType::MEMORY_USED
+                | DataType::MEMORY_FREE
+                | DataType::MEMORY_AVAILABLE
+                | DataType::CUSTOM_MEMORY(_)
+                | DataType::CUSTOM_MEMORY_PER_PID(_)
+                | DataType::USER_MEMORY
+                | DataType::CGROUP_MEMORY(_)
+                | DataType::CGROUP_MEMORY_MAX(_)
+        ) || self.is_meminfo()
+    }
+    pub fn is_meminfo(&self) -> bool {
+        matches!(
+            self,
+            DataType::MEMORY_CACHED
+                | DataType::MEMORY_BUFFERS
+                | DataType::MEMORY_SHMEM
+                | DataType::MEMORY_DIRTY
+                | DataType::MEMORY_WRITEBACK
+                | DataType::MEMORY_SLAB_RECLAIMABLE
+                | DataType::MEMORY_SLAB_UNRECLAIMABLE
+                | DataType::MEMORY_ANON
+        )
+    }
+    // Parts of memory which don't overlap, so they can be stacked on top of each other
+    // Shmem, dirty and writeback are already included in cached memory
+    pub fn is_memory_stacked_part(&self) -> bool {
+        matches!(
+            self,
+            DataType::MEMORY_FREE
+                | DataType::MEMORY_ANON
+                | DataType::MEMORY_BUFFERS
+                | DataType::MEMORY_CACHED
+                | DataType::MEMORY_SLAB_RECLAIMABLE
+                | DataType::MEMORY_SLAB_UNRECLAIMABLE
         )
     }
     pub fn is_swap(&self) -> bool {
         matches!(self, DataType::SWAP_USED | DataType::SWAP_FREE)
     }
+    // Swap rates are shown in swap group, but on secondary axis, because they use different units
+    pub fn is_swap_rate(&self) -> bool {
+        matches!(self, DataType::SWAP_IN | DataType::SWAP_OUT)
+    }
+    pub fn is_virtual_memory(&self) -> bool {
+        matches!(
+            self,
+            DataType::PAGE_FAULTS_MINOR | DataType::PAGE_FAULTS_MAJOR | DataType::PAGE_SCAN | DataType::PAGE_STEAL | DataType::OOM_KILLS
+        )
+    }
     pub fn is_cpu(&self) -> bool {
-        matches!(self, DataType::CPU_USAGE_TOTAL | DataType::CPU_USAGE_PER_CORE | DataType::CUSTOM_CPU(_))
+        matches!(
+            self,
+            DataType::CPU_USAGE_TOTAL
+                | DataType::CPU_USAGE_PER_CORE
+                | DataType::CUSTOM_CPU(_)
+                | DataType::CUSTOM_CPU_PER_PID(_)
+                | DataType::USER_CPU
+                | DataType::CGROUP_CPU(_)
+        ) || self.is_cpu_state()
+            || self.is_cpu_state_per_core()
+    }
+    pub fn is_cpu_state(&self) -> bool {
+        matches!(
+            self,
+            DataType::CPU_USER
+                | DataType::CPU_NICE
+                | DataType::CPU_SYSTEM
+                | DataType::CPU_IDLE
+                | DataType::CPU_IOWAIT
+                | DataType::CPU_IRQ
+                | DataType::CPU_SOFTIRQ
+                | DataType::CPU_STEAL
+        )
+    }
+    pub fn is_cpu_state_per_core(&self) -> bool {
+        matches!(
+            self,
+            DataType::CPU_USER_PER_CORE
+                | DataType::CPU_NICE_PER_CORE
+                | DataType::CPU_SYSTEM_PER_CORE
+                | DataType::CPU_IDLE_PER_CORE
+                | DataType::CPU_IOWAIT_PER_CORE
+                | DataType::CPU_IRQ_PER_CORE
+                | DataType::CPU_SOFTIRQ_PER_CORE
+                | DataType::CPU_STEAL_PER_CORE
+        )
+    }
+    pub fn is_disk(&self) -> bool {
+        matches!(
+            self,
+            DataType::DISK_READ_SPEED | DataType::DISK_WRITE_SPEED | DataType::DISK_READ_IOPS | DataType::DISK_WRITE_IOPS | DataType::DISK_BUSY
+        )
+    }
+    pub fn is_network(&self) -> bool {
+        matches!(
+            self,
+            DataType::NETWORK_RX_SPEED
+                | DataType::NETWORK_TX_SPEED
+                | DataType::NETWORK_RX_PACKETS
+                | DataType::NETWORK_TX_PACKETS
+                | DataType::NETWORK_RX_ERRORS
+                | DataType::NETWORK_TX_ERRORS
+                | DataType::NETWORK_RX_DROPPED
+                | DataType::NETWORK_TX_DROPPED
+        )
+    }
+    // Load average is shown together with pressure, because both show how much tasks are waiting for resources
+    pub fn is_pressure(&self) -> bool {
+        matches!(
+            self,
+            DataType::LOAD_AVERAGE_1
+                | DataType::LOAD_AVERAGE_5
+                | DataType::LOAD_AVERAGE_15
+                | DataType::PRESSURE_CPU_SOME
+                | DataType::PRESSURE_CPU_FULL
+                | DataType::PRESSURE_MEMORY_SOME
+                | DataType::PRESSURE_MEMORY_FULL
+                | DataType::PRESSURE_IO_SOME
+                | DataType::PRESSURE_IO_FULL
+        )
+    }
+    pub fn is_cpu_frequency(&self) -> bool {
+        self == &DataType::CPU_FREQUENCY_PER_CORE
+    }
+    pub fn is_kernel_activity(&self) -> bool {
+        matches!(
+            self,
+            DataType::CONTEXT_SWITCHES | DataType::INTERRUPTS | DataType::FORKS | DataType::PROCESSES_RUNNING | DataType::PROCESSES_BLOCKED
+        )
+    }
+    pub fn is_tasks(&self) -> bool {
+        matches!(
+            self,
+            DataType::TASKS_PROCESSES
+                | DataType::TASKS_THREADS
+                | DataType::TASKS_RUNNING
+                | DataType::TASKS_SLEEPING
+                | DataType::TASKS_DISK_SLEEP
+                | DataType::TASKS_ZOMBIE
+        )
+    }
+    pub fn is_descriptors(&self) -> bool {
+        matches!(
+            self,
+            DataType::OPEN_FILES
+                | DataType::OPEN_FILES_LIMIT
+                | DataType::SOCKETS_USED
+                | DataType::SOCKETS_TCP_IN_USE
+                | DataType::SOCKETS_TCP_ORPHAN
+                | DataType::SOCKETS_TCP_TIME_WAIT
+                | DataType::SOCKETS_TCP_MEMORY
+                | DataType::SOCKETS_UDP_IN_USE
+                | DataType::SOCKETS_UDP_MEMORY
+        )
+    }
+    pub fn is_tcp_states(&self) -> bool {
+        matches!(
+            self,
+            DataType::TCP_ESTABLISHED
+                | DataType::TCP_SYN_SENT
+                | DataType::TCP_SYN_RECV
+                | DataType::TCP_FIN_WAIT1
+                | DataType::TCP_FIN_WAIT2
+                | DataType::TCP_TIME_WAIT
+                | DataType::TCP_CLOSE
+                | DataType::TCP_CLOSE_WAIT
+                | DataType::TCP_LAST_ACK
+                | DataType::TCP_LISTEN
+                | DataType::TCP_CLOSING
+        )
+    }
+    pub fn is_temperature(&self) -> bool {
+        self == &DataType::TEMPERATURE
+    }
+    // Additional data of tracked processes, which use different units than cpu and memory usage
+    pub fn is_process_details(&self) -> bool {
+        matches!(
+            self,
+            DataType::CUSTOM_THREADS(_)
+                | DataType::CUSTOM_OPEN_FILES(_)
+                | DataType::CUSTOM_VIRTUAL_MEMORY(_)
+                | DataType::CUSTOM_VOLUNTARY_CONTEXT_SWITCHES(_)
+                | DataType::CUSTOM_INVOLUNTARY_CONTEXT_SWITCHES(_)
+                | DataType::CUSTOM_DISK_READ(_)
+                | DataType::CUSTOM_DISK_WRITE(_)
+        )
+    }
+    pub fn is_process_memory(&self) -> bool {
+        matches!(
+            self,
+            DataType::CUSTOM_PSS(_)
+                | DataType::CUSTOM_PSS_ANON(_)
+                | DataType::CUSTOM_PSS_FILE(_)
+                | DataType::CUSTOM_PSS_SHMEM(_)
+                | DataType::CUSTOM_SWAP_PSS(_)
+        )
+    }
+    pub fn is_power(&self) -> bool {
+        matches!(
+            self,
+            DataType::POWER_CAPACITY | DataType::POWER_STATUS | DataType::POWER_VOLTAGE | DataType::POWER_CURRENT | DataType::POWER_USAGE
+        )
+    }
+    pub fn is_disk_space(&self) -> bool {
+        matches!(
+            self,
+            DataType::MOUNT_USED(_) | DataType::MOUNT_AVAILABLE(_) | DataType::MOUNT_INODES_USED(_)
+        )
+    }
+    // Cgroup memory and cpu usage are shown together with system wide values
+    pub fn is_cgroup(&self) -> bool {
+        matches!(
+            self,
+            DataType::CGROUP_THROTTLED(_) | DataType::CGROUP_IO_READ(_) | DataType::CGROUP_IO_WRITE(_)
+        )
+    }
+    // Values of such data are stored as multiple items split by ; in a single column
+    pub fn is_per_item(&self) -> bool {
+        matches!(self, DataType::CPU_USAGE_PER_CORE | DataType::CPU_FREQUENCY_PER_CORE)
+            || self.is_cpu_state_per_core()
+            || self.is_disk()
+            || self.is_network()
+            || self.is_temperature()
+            || self.is_power()
+    }
+    // Set of processes matching search text changes over time, so values are stored as pid:value items split by ;
+    pub fn is_per_pid(&self) -> bool {
+        matches!(self, DataType::CUSTOM_CPU_PER_PID(_) | DataType::CUSTOM_MEMORY_PER_PID(_))
+    }
+    // The same as above, but with user:value items, because top users may change
+    pub fn is_per_user(&self) -> bool {
+        matches!(self, DataType::USER_CPU | DataType::USER_MEMORY)
+    }
+    pub fn group(&self) -> Option<GeneralInfoGroup> {
+        if self.is_cpu() {
+            Some(GeneralInfoGroup::CPU)
+        } else if self.is_cpu_frequency() {
+            Some(GeneralInfoGroup::CPU_FREQUENCY)
+        } else if self.is_memory() {
+            Some(GeneralInfoGroup::MEMORY)
+        } else if self.is_swap() || self.is_swap_rate() {
+            Some(GeneralInfoGroup::SWAP)
+        } else if self.is_virtual_memory() {
+            Some(GeneralInfoGroup::VIRTUAL_MEMORY)
+        } else if self.is_disk() {
+            Some(GeneralInfoGroup::DISK)
+        } else if self.is_network() {
+            Some(GeneralInfoGroup::NETWORK)
+        } else if self.is_pressure() {
+            Some(GeneralInfoGroup::PRESSURE)
+        } else if self.is_temperature() {
+            Some(GeneralInfoGroup::TEMPERATURE)
+        } else if self.is_power() {
+            Some(GeneralInfoGroup::POWER)
+        } else if self.is_kernel_activity() {
+            Some(GeneralInfoGroup::KERNEL_ACTIVITY)
+        } else if self.is_tasks() {
+            Some(GeneralInfoGroup::TASKS)
+        } else if self.is_descriptors() {
+            Some(GeneralInfoGroup::DESCRIPTORS)
+        } else if self.is_tcp_states() {
+            Some(GeneralInfoGroup::TCP_STATES)
+        } else if self.is_disk_space() {
+            Some(GeneralInfoGroup::DISK_SPACE)
+        } else if self.is_process_memory() {
+            Some(GeneralInfoGroup::PROCESS_MEMORY)
+        } else if self.is_process_details() {
+            Some(GeneralInfoGroup::PROCESS_DETAILS)
+        } else if self.is_cgroup() {
+            Some(GeneralInfoGroup::CGROUP)
+        } else {
+            None
+        }
     }
     pub fn pretty_print(&self) -> String {
         match self {
             DataType::SECONDS_SINCE_START => "Unix timestamp".to_string(),
             DataType::CPU_USAGE_TOTAL => "CPU usage total".to_string(),
             DataType::CPU_USAGE_PER_CORE => "CPU usage per core".to_string(),
+            DataType::CPU_FREQUENCY_PER_CORE => "CPU frequency per core".to_string(),
+            DataType::CPU_USER | DataType::CPU_USER_PER_CORE => "CPU user".to_string(),
+            DataType::CPU_NICE | DataType::CPU_NICE_PER_CORE => "CPU nice".to_string(),
+            DataType::CPU_SYSTEM | DataType::CPU_SYSTEM_PER_CORE => "CPU system".to_string(),
+            DataType::CPU_IDLE | DataType::CPU_IDLE_PER_CORE => "CPU idle".to_string(),
+            DataType::CPU_IOWAIT | DataType::CPU_IOWAIT_PER_CORE => "CPU iowait".to_string(),
+            DataType::CPU_IRQ | DataType::CPU_IRQ_PER_CORE => "CPU irq".to_string(),
+            DataType::CPU_SOFTIRQ | DataType::CPU_SOFTIRQ_PER_CORE => "CPU softirq".to_string(),
+            DataType::CPU_STEAL | DataType::CPU_STEAL_PER_CORE => "CPU steal".to_string(),
             DataType::MEMORY_USED => "Memory used".to_string(),
             DataType::MEMORY_FREE => "Memory free".to_string(),
             DataType::SWAP_FREE => "Swap free".to_string(),
             DataType::SWAP_USED => "Swap used".to_string(),
+            DataType::SWAP_IN => "Swap in[pages/s]".to_string(),
+            DataType::SWAP_OUT => "Swap out[pages/s]".to_string(),
             DataType::MEMORY_AVAILABLE => "Memory available".to_string(),
+            DataType::MEMORY_CACHED => "Memory cached".to_string(),
+            DataType::MEMORY_BUFFERS => "Memory buffers".to_string(),
+            DataType::MEMORY_SHMEM => "Memory shared".to_string(),
+            DataType::MEMORY_DIRTY => "Memory dirty".to_string(),
+            DataType::MEMORY_WRITEBACK => "Memory writeback".to_string(),
+            DataType::MEMORY_SLAB_RECLAIMABLE => "Memory slab reclaimable".to_string(),
+            DataType::MEMORY_SLAB_UNRECLAIMABLE => "Memory slab unreclaimable".to_string(),
+            DataType::MEMORY_ANON => "Memory anonymous".to_string(),
+            DataType::PAGE_FAULTS_MINOR => "Minor page faults/s".to_string(),
+            DataType::PAGE_FAULTS_MAJOR => "Major page faults/s".to_string(),
+            DataType::PAGE_SCAN => "Pages scanned/s".to_string(),
+            DataType::PAGE_STEAL => "Pages reclaimed/s".to_string(),
+            DataType::OOM_KILLS => "OOM kills since boot".to_string(),
+            DataType::DISK_READ_SPEED => "Disk read[MB/s]".to_string(),
+            DataType::DISK_WRITE_SPEED => "Disk write[MB/s]".to_string(),
+            DataType::DISK_READ_IOPS => "Disk read IOPS".to_string(),
+            DataType::DISK_WRITE_IOPS => "Disk write IOPS".to_string(),
+            DataType::DISK_BUSY => "Disk busy[%]".to_string(),
+            DataType::NETWORK_RX_SPEED => "Network received[MB/s]".to_string(),
+            DataType::NETWORK_TX_SPEED => "Network sent[MB/s]".to_string(),
+            DataType::NETWORK_RX_PACKETS => "Network received packets/s".to_string(),
+            DataType::NETWORK_TX_PACKETS => "Network sent packets/s".to_string(),
+            DataType::NETWORK_RX_ERRORS => "Network receive errors/s".to_string(),
+            DataType::NETWORK_TX_ERRORS => "Network send errors/s".to_string(),
+            DataType::NETWORK_RX_DROPPED => "Network received dropped/s".to_string(),
+            DataType::NETWORK_TX_DROPPED => "Network sent dropped/s".to_string(),
+            DataType::LOAD_AVERAGE_1 => "Load average 1 min".to_string(),
+            DataType::LOAD_AVERAGE_5 => "Load average 5 min".to_string(),
+            DataType::LOAD_AVERAGE_15 => "Load average 15 min".to_string(),
+            DataType::PRESSURE_CPU_SOME => "CPU pressure some[%]".to_string(),
+            DataType::PRESSURE_CPU_FULL => "CPU pressure full[%]".to_string(),
+            DataType::PRESSURE_MEMORY_SOME => "Memory pressure some[%]".to_string(),
+            DataType::PRESSURE_MEMORY_FULL => "Memory pressure full[%]".to_string(),
+            DataType::PRESSURE_IO_SOME => "IO pressure some[%]".to_string(),
+            DataType::PRESSURE_IO_FULL => "IO pressure full[%]".to_string(),
+            DataType::CONTEXT_SWITCHES => "Context switches/s".to_string(),
+            DataType::INTERRUPTS => "Interrupts/s".to_string(),
+            DataType::FORKS => "Forks/s".to_string(),
+            DataType::PROCESSES_RUNNING => "Processes running".to_string(),
+            DataType::PROCESSES_BLOCKED => "Processes blocked".to_string(),
+            DataType::TASKS_PROCESSES => "Processes".to_string(),
+            DataType::TASKS_THREADS => "Threads".to_string(),
+            DataType::TASKS_RUNNING => "Tasks running".to_string(),
+            DataType::TASKS_SLEEPING => "Tasks sleeping".to_string(),
+            DataType::TASKS_DISK_SLEEP => "Tasks in uninterruptible sleep(D)".to_string(),
+            DataType::TASKS_ZOMBIE => "Zombie processes".to_string(),
+            DataType::OPEN_FILES => "Open files".to_string(),
+            DataType::OPEN_FILES_LIMIT => "Open files limit".to_string(),
+            DataType::SOCKETS_USED => "Sockets used".to_string(),
+            DataType::SOCKETS_TCP_IN_USE => "TCP sockets in use".to_string(),
+            DataType::SOCKETS_TCP_ORPHAN => "TCP sockets orphaned".to_string(),
+            DataType::SOCKETS_TCP_TIME_WAIT => "TCP sockets in TIME_WAIT".to_string(),
+            DataType::SOCKETS_TCP_MEMORY => "TCP sockets memory[MB]".to_string(),
+            DataType::SOCKETS_UDP_IN_USE => "UDP sockets in use".to_string(),
+            DataType::SOCKETS_UDP_MEMORY => "UDP sockets memory[MB]".to_string(),
+            DataType::TEMPERATURE => "Temperature".to_string(),
+            DataType::POWER_CAPACITY => "Battery capacity[%]".to_string(),
+            DataType::POWER_STATUS => "Status(0 discharging, 1 not charging, 2 charging, 3 full)".to_string(),
+            DataType::POWER_VOLTAGE => "Voltage[V]".to_string(),
+            DataType::POWER_CURRENT => "Current[A]".to_string(),
+            DataType::POWER_USAGE => "Power[W]".to_string(),
+            DataType::TCP_ESTABLISHED => "TCP ESTABLISHED".to_string(),
+            DataType::TCP_SYN_SENT => "TCP SYN_SENT".to_string(),
+            DataType::TCP_SYN_RECV => "TCP SYN_RECV".to_string(),
+            DataType::TCP_FIN_WAIT1 => "TCP FIN_WAIT1".to_string(),
+            DataType::TCP_FIN_WAIT2 => "TCP FIN_WAIT2".to_string(),
+            DataType::TCP_TIME_WAIT => "TCP TIME_WAIT".to_string(),
+            DataType::TCP_CLOSE => "TCP CLOSE".to_string(),
+            DataType::TCP_CLOSE_WAIT => "TCP CLOSE_WAIT".to_string(),
+            DataType::TCP_LAST_ACK => "TCP LAST_ACK".to_string(),
+            DataType::TCP_LISTEN => "TCP LISTEN".to_string(),
+            DataType::TCP_CLOSING => "TCP CLOSING".to_string(),
+            DataType::USER_CPU => "CPU usage of user".to_string(),
+            DataType::USER_MEMORY => "Memory usage of user".to_string(),
             DataType::CUSTOM_CPU((_, name)) => format!("CPU usage for {name}"),
             DataType::CUSTOM_MEMORY((_, name)) => format!("Memory usage for {name}"),
+            DataType::CUSTOM_CPU_PER_PID((_, name)) => format!("CPU usage for {name}"),
+            DataType::CUSTOM_MEMORY_PER_PID((_, name)) => format!("Memory usage for {name}"),
+            DataType::CUSTOM_THREADS((_, name)) => format!("Threads of {name}"),
+            DataType::CUSTOM_OPEN_FILES((_, name)) => format!("Open files of {name}"),
+            DataType::CUSTOM_VIRTUAL_MEMORY((_, name)) => format!("Virtual memory of {name}[MB]"),
+            DataType::CUSTOM_VOLUNTARY_CONTEXT_SWITCHES((_, name)) => format!("Voluntary context switches/s of {name}"),
+            DataType::CUSTOM_INVOLUNTARY_CONTEXT_SWITCHES((_, name)) => format!("Involuntary context switches/s of {name}"),
+            DataType::CUSTOM_DISK_READ((_, name)) => format!("Disk read of {name}[MB/s]"),
+            DataType::CUSTOM_DISK_WRITE((_, name)) => format!("Disk write of {name}[MB/s]"),
+            DataType::CUSTOM_PSS((_, name)) => format!("PSS of {name}"),
+            DataType::CUSTOM_PSS_ANON((_, name)) => format!("PSS anonymous of {name}"),
+            DataType::CUSTOM_PSS_FILE((_, name)) => format!("PSS file of {name}"),
+            DataType::CUSTOM_PSS_SHMEM((_, name)) => format!("PSS shared of {name}"),
+            DataType::CUSTOM_SWAP_PSS((_, name)) => format!("Swap PSS of {name}"),
+            DataType::MOUNT_USED((_, mount_point)) => format!("Disk space used on {mount_point}"),
+            DataType::MOUNT_AVAILABLE((_, mount_point)) => format!("Disk space available on {mount_point}"),
+            DataType::MOUNT_INODES_USED((_, mount_point)) => format!("Inodes used on {mount_point}[%]"),
+            DataType::CGROUP_MEMORY((_, name)) => format!("Memory usage for cgroup {name}"),
+            DataType::CGROUP_MEMORY_MAX((_, name)) => format!("Memory limit for cgroup {name}"),
+            DataType::CGROUP_CPU((_, name)) => format!("CPU usage for cgroup {name}"),
+            DataType::CGROUP_THROTTLED((_, name)) => format!("Throttled periods/s for cgroup {name}"),
+            DataType::CGROUP_IO_READ((_, name)) => format!("IO read for cgroup {name}[MB/s]"),
+            DataType::CGROUP_IO_WRITE((_, name)) => format!("IO write for cgroup {name}[MB/s]"),
         }
     }
 }
@@ -77,14 +805,48 @@ pub enum HeaderValues {
     INTERVAL_SECONDS,
     APP_VERSION,
     UNIX_TIMESTAMP_START_TIME,
+    DISK_DEVICES,
+    NETWORK_INTERFACES,
+    TEMPERATURE_SENSORS,
+    POWER_SUPPLIES,
+    EFFECTIVE_MEMORY_LIMIT,
+    EFFECTIVE_CPU_LIMIT,
+    TCP_LOCAL_PORTS,
+    TOP_PROCESSES,
+    PROCESS_EVENTS,
+    COMMAND,
+    COMMAND_EXIT_STATUS,
+    COMMAND_WALL_TIME_SECONDS,
 }
 
 #[derive(Clone, EnumString, EnumIter, ValueEnum, Debug, Eq, PartialEq, Default, Display, Deserialize, Hash, Copy)]
 pub enum GeneralInfoGroup {
     #[default]
     CPU,
+    CPU_FREQUENCY,
     MEMORY,
     SWAP,
+    VIRTUAL_MEMORY,
+    DISK,
+    NETWORK,
+    PRESSURE,
+    TEMPERATURE,
+    POWER,
+    DISK_SPACE,
+    KERNEL_ACTIVITY,
+    TASKS,
+    DESCRIPTORS,
+    TCP_STATES,
+    PROCESS_DETAILS,
+    PROCESS_MEMORY,
+    CGROUP,
+}
+
+#[derive(Clone, EnumString, EnumIter, ValueEnum, Debug, Eq, PartialEq, Default, Display, Deserialize, Hash, Copy)]
+pub enum ProcessEventKind {
+    #[default]
+    STARTED,
+    EXITED,
 }
 
 #[derive(Clone, EnumString, EnumIter, ValueEnum, Debug, Eq, PartialEq, Default, Display, Deserialize, Hash, Copy)]
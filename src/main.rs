@@ -18,11 +18,12 @@ mod csv_file_loader;
 mod enums;
 mod model;
 mod ploty_creator;
+mod stats;
 
 #[tokio::main]
 async fn main() {
     let cli_model = parse_cli();
-    let settings: Settings = cli_model.into();
+    let mut settings: Settings = cli_model.into();
 
     let config = ConfigBuilder::new().set_level(settings.log_level.into()).build();
     TermLogger::init(config, TerminalMode::Mixed, ColorChoice::Auto).unwrap();
@@ -43,7 +44,7 @@ async fn main() {
             creating_duration
         );
 
-        if let Err(e) = collect_data(&mut sys, &settings).await {
+        if let Err(e) = collect_data(&mut sys, &mut settings).await {
             error!("{e}");
             process::exit(1);
         };
@@ -1,17 +1,24 @@
 use std::collections::hash_set::Iter;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
 use std::process;
 use std::time::SystemTime;
 
+use anyhow::{Context, Error};
+use regex::Regex;
 use serde::Deserialize;
-use sysinfo::{Process, System};
+use sysinfo::{Process, System, Uid, Users};
 
-use crate::cli::Cli;
-use crate::enums::{AppMode, DataType, GeneralInfoGroup, LogLev, SimpleDataCollectionMode};
+use crate::cli::{Cli, Commands};
+use crate::enums::{AppMode, DataType, GeneralInfoGroup, LogLev, ProcessEventKind, SimpleDataCollectionMode};
+use crate::ploty_creator::MAXIMUM_PLOT_GROUPS;
+use crate::stats::process_details::{ProcessCpuTracker, ProcessDetailsTracker, SmapsRollup};
 
 #[derive(Default, Clone, Debug, Deserialize)]
 pub struct CollectedItemModels {
-    // pub collected_data_names: Vec<DataType>,
+    // Order of columns in data file, used to always show traces in the same order
+    pub collected_data_names: Vec<DataType>,
     pub collected_data: HashMap<DataType, Vec<String>>,
     pub collected_groups: Vec<GeneralInfoGroup>,
     pub memory_total: f64,
@@ -19,6 +26,77 @@ pub struct CollectedItemModels {
     pub cpu_core_count: usize,
     pub check_interval: f32,
     pub start_time: f64,
+    pub disk_devices: Vec<String>,
+    pub network_interfaces: Vec<String>,
+    pub temperature_sensors: Vec<String>,
+    pub power_supplies: Vec<String>,
+    pub tcp_local_ports: Vec<String>,
+    // Limits of cgroup in which app was running, in MB and cores
+    pub effective_memory_limit: Option<f64>,
+    pub effective_cpu_limit: Option<f64>,
+    // Selectors are kept only to show them in plot, e.g. "FIREFOX - cmd:firefox"
+    pub process_selectors: Vec<String>,
+    pub process_exclusions: Vec<String>,
+    // Sorted by time, empty when top processes were not collected
+    pub top_processes: Vec<TopProcessesEntry>,
+    pub process_events: Vec<ProcessEventEntry>,
+    pub process_events_filters: Vec<String>,
+    // Available only when app was running command given by user
+    pub command: Option<String>,
+    pub command_exit_status: Option<String>,
+    pub command_wall_time_seconds: Option<f64>,
+}
+
+impl CollectedItemModels {
+    // Iterating over hashmap would change order of stacked traces between runs
+    pub fn ordered_collected_data(&self) -> impl Iterator<Item = (&DataType, &Vec<String>)> {
+        self.collected_data_names
+            .iter()
+            .filter_map(|data_type| self.collected_data.get_key_value(data_type))
+    }
+
+    // Names of items stored in per item data, used both to split data and as trace names
+    pub fn get_item_names(&self, data_type: &DataType) -> Vec<String> {
+        if matches!(data_type, DataType::CPU_USAGE_PER_CORE | DataType::CPU_FREQUENCY_PER_CORE) || data_type.is_cpu_state_per_core() {
+            (0..self.cpu_core_count).map(|idx| format!("Core {idx}")).collect()
+        } else if data_type.is_disk() {
+            self.disk_devices.clone()
+        } else if data_type.is_network() {
+            self.network_interfaces.clone()
+        } else if data_type.is_temperature() {
+            self.temperature_sensors.clone()
+        } else if data_type.is_power() {
+            self.power_supplies.clone()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+// Values are kept as strings, the same as other loaded data
+#[derive(Default, Clone, Debug, Deserialize)]
+pub struct TopProcessEntry {
+    pub pid: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Default, Clone, Debug, Deserialize)]
+pub struct TopProcessesEntry {
+    pub seconds_since_start: f64,
+    pub top_cpu: Vec<TopProcessEntry>,
+    pub top_memory: Vec<TopProcessEntry>,
+}
+
+#[derive(Default, Clone, Debug, Deserialize)]
+pub struct ProcessEventEntry {
+    pub seconds_since_start: f64,
+    pub kind: ProcessEventKind,
+    pub pid: String,
+    pub ppid: String,
+    pub name: String,
+    pub lifetime_seconds: String,
+    pub cmd: String,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -27,7 +105,11 @@ pub struct CustomProcessData {
     pub name: String,
     pub cmd_string: String,
     pub memory_usage: u64,
-    pub cpu_usage: f32,
+    // None when usage is not known yet
+    pub cpu_usage: Option<f32>,
+    pub cpu_tracker: ProcessCpuTracker,
+    pub details: ProcessDetailsTracker,
+    pub smaps_rollup: Option<SmapsRollup>,
 }
 
 impl CustomProcessData {
@@ -42,7 +124,10 @@ impl CustomProcessData {
                 .collect::<Vec<_>>()
                 .join(" "),
             memory_usage: process.memory(),
-            cpu_usage: process.cpu_usage(),
+            cpu_usage: None,
+            cpu_tracker: ProcessCpuTracker::default(),
+            details: ProcessDetailsTracker::default(),
+            smaps_rollup: None,
         }
     }
 }
@@ -54,6 +139,8 @@ pub struct ProcessCache {
     // Processes were checked if can be used in data collection
     pub processes_checked_to_be_used: HashSet<usize>,
     pub process_used: Vec<Option<CustomProcessData>>,
+    // Used only by searches which track all matching processes, sorted by pid
+    pub all_processes_used: Vec<Vec<CustomProcessData>>,
 }
 impl ProcessCache {
     pub fn new_with_size(size: usize, sys: &System) -> Self {
@@ -66,13 +153,14 @@ impl ProcessCache {
         let mut processes_checked_to_be_used = HashSet::default();
         processes_checked_to_be_used.insert(process::id() as usize);
 
-        let mut processes_usage_updated = sys.processes().iter().map(|(pid, _)| (*pid).into()).collect::<HashSet<usize>>();
+        let mut processes_usage_updated = sys.processes().keys().map(|pid| (*pid).into()).collect::<HashSet<usize>>();
         processes_usage_updated.insert(process::id() as usize);
 
         ProcessCache {
             processes_usage_updated,
             processes_checked_to_be_used,
             process_used,
+            all_processes_used: vec![Vec::new(); size],
         }
     }
 
@@ -97,10 +185,116 @@ impl ProcessCache {
     }
 }
 
-#[derive(Default, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct FindingStruct {
     pub graph_name: String,
-    pub search_text: String,
+    pub selector: ProcessSelector,
+    // Usage of all children, grandchildren etc. is added to usage of found process
+    pub include_children: bool,
+    // Every matching process is tracked separately, instead only one with shortest command
+    pub all_matching: bool,
+}
+
+// Written as KIND:VALUE e.g. pid:1234 or regex:worker --id [0-9]+
+// Text without : is searched in process run command, like in older versions, but unknown kinds are rejected, so older
+// search texts containing : must be now prefixed with cmd:
+#[derive(Clone, Debug)]
+pub enum ProcessSelector {
+    Pid(usize),
+    Name(String),
+    Exe(PathBuf),
+    // Uid and name or uid given by user
+    User(Uid, String),
+    Cwd(PathBuf),
+    Regex(Regex),
+    Cmd(String),
+}
+
+impl ProcessSelector {
+    // Text containing : must be prefixed with cmd:, so misspelled kind is not silently treated as searched text
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let Some((kind, value)) = text.split_once(':') else {
+            return Ok(ProcessSelector::Cmd(text.to_string()));
+        };
+        let selector = match kind {
+            "pid" => ProcessSelector::Pid(value.parse().context(format!("Failed to parse pid in selector {text}"))?),
+            "name" => ProcessSelector::Name(value.to_string()),
+            "exe" => ProcessSelector::Exe(PathBuf::from(value)),
+            "user" => ProcessSelector::User(
+                find_uid(value).context(format!("Failed to find user in selector {text}"))?,
+                value.to_string(),
+            ),
+            "cwd" => ProcessSelector::Cwd(PathBuf::from(value)),
+            "regex" => ProcessSelector::Regex(Regex::new(value).context(format!("Failed to parse regex in selector {text}"))?),
+            "cmd" => ProcessSelector::Cmd(value.to_string()),
+            _ => {
+                return Err(Error::msg(format!(
+                    "Unknown selector kind \"{kind}\" in {text}, expected one of pid, name, exe, user, cwd, regex or cmd - use cmd:{text} to search text containing :"
+                )))
+            }
+        };
+        Ok(selector)
+    }
+
+    // Cmd is passed, because it is already joined by caller
+    pub fn matches(&self, pid: usize, process: &Process, cmd: &str) -> bool {
+        self.matches_values(
+            pid,
+            &process.name().to_string_lossy(),
+            process.exe(),
+            process.user_id().map(|e| **e),
+            process.cwd(),
+            cmd,
+        )
+    }
+
+    // Used also for processes read directly from /proc, without sysinfo
+    pub fn matches_values(&self, pid: usize, name: &str, exe: Option<&Path>, uid: Option<u32>, cwd: Option<&Path>, cmd: &str) -> bool {
+        match self {
+            ProcessSelector::Pid(selected_pid) => pid == *selected_pid,
+            ProcessSelector::Name(selected_name) => name == selected_name.as_str(),
+            ProcessSelector::Exe(selected_exe) => exe == Some(selected_exe.as_path()),
+            ProcessSelector::User(selected_uid, _) => uid == Some(**selected_uid),
+            ProcessSelector::Cwd(selected_cwd) => cwd == Some(selected_cwd.as_path()),
+            ProcessSelector::Regex(regex) => regex.is_match(cmd),
+            ProcessSelector::Cmd(text) => cmd.contains(text.as_str()),
+        }
+    }
+}
+
+impl Display for ProcessSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessSelector::Pid(pid) => write!(f, "pid:{pid}"),
+            ProcessSelector::Name(name) => write!(f, "name:{name}"),
+            ProcessSelector::Exe(exe) => write!(f, "exe:{}", exe.display()),
+            ProcessSelector::User(_, user) => write!(f, "user:{user}"),
+            ProcessSelector::Cwd(cwd) => write!(f, "cwd:{}", cwd.display()),
+            ProcessSelector::Regex(regex) => write!(f, "regex:{}", regex.as_str()),
+            ProcessSelector::Cmd(text) => write!(f, "cmd:{text}"),
+        }
+    }
+}
+
+// User may be given by name or by uid
+pub fn find_uid(user: &str) -> Option<Uid> {
+    if let Ok(uid) = user.parse::<Uid>() {
+        return Some(uid);
+    }
+    Users::new_with_refreshed_list().iter().find(|e| e.name() == user).map(|e| e.id().clone())
+}
+
+pub fn find_user_name(uid: u32) -> Option<String> {
+    Users::new_with_refreshed_list()
+        .iter()
+        .find(|e| **e.id() == uid)
+        .map(|e| e.name().to_string())
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct CgroupStruct {
+    pub graph_name: String,
+    pub cgroup_path: String,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -119,7 +313,26 @@ pub struct Settings {
     // pub use_web_gl: bool,
     pub backup_number: u32,
     pub maximum_data_file_size_bytes: usize,
+    pub skip_virtual_network_interfaces: bool,
+    pub sysfs_path: String,
+    pub stacked_memory_plot: bool,
     pub process_cmd_to_search: Vec<FindingStruct>,
+    pub sum_all_processes: bool,
+    pub process_exclusions: Vec<ProcessSelector>,
+    pub extended_process_metrics: bool,
+    pub process_pss_memory: bool,
+    pub mount_points_to_check: Vec<String>,
+    pub tcp_local_ports: Vec<u16>,
+    pub users_to_check: Vec<String>,
+    pub top_users: usize,
+    pub top_processes: usize,
+    pub top_processes_interval: usize,
+    pub process_events: bool,
+    pub process_events_filters: Vec<ProcessSelector>,
+    pub command_to_run: Vec<String>,
+    pub cgroups_to_check: Vec<CgroupStruct>,
+    pub cgroup_path: String,
+    pub scale_to_cgroup_limits: bool,
     pub need_to_refresh_processes: bool,
     pub start_time: f64,
 }
@@ -129,24 +342,48 @@ impl From<Cli> for Settings {
         let process_to_search: Vec<_> = cli
             .process_cmd_to_search
             .iter()
-            .map(|e| {
-                if e.contains('=') || e.contains(',') {
-                    eprintln!("{e} - cannot use here = or ,");
-                    process::exit(1);
-                }
-                let split = e.split('|').collect::<Vec<_>>();
-                if split.len() != 2 {
-                    eprintln!("{e} - should contains two parts split by |");
-                    process::exit(1);
-                }
+            .map(|e| (e, false, false))
+            .chain(cli.process_tree_to_search.iter().map(|e| (e, true, false)))
+            .chain(cli.all_processes_to_search.iter().map(|e| (e, false, true)))
+            .map(|(e, include_children, all_matching)| {
+                let (graph_name, selector_text) = split_named_item(e);
                 FindingStruct {
-                    graph_name: split[0].to_string(),
-                    search_text: split[1].to_string(),
+                    graph_name,
+                    selector: parse_process_selector(&selector_text),
+                    include_children,
+                    all_matching,
                 }
             })
             .collect();
+        let cgroups_to_check: Vec<_> = cli
+            .cgroups_to_check
+            .iter()
+            .map(|e| {
+                let (graph_name, cgroup_path) = split_named_item(e);
+                CgroupStruct { graph_name, cgroup_path }
+            })
+            .collect();
 
-        Settings {
+        let process_exclusions: Vec<_> = cli.process_exclusions.iter().map(|e| parse_header_process_selector(e)).collect();
+        let process_events_filters: Vec<_> = cli.process_events_filters.iter().map(|e| parse_header_process_selector(e)).collect();
+
+        let command_to_run = match cli.command {
+            Some(Commands::Run { command }) => command,
+            None => Vec::new(),
+        };
+        if !command_to_run.is_empty() && cli.app_mode == AppMode::CONVERT {
+            eprintln!("Command to run can be used only in collect modes");
+            process::exit(1);
+        }
+
+        for mount_point in &cli.mount_points_to_check {
+            if mount_point.contains('=') || mount_point.contains(',') {
+                eprintln!("{mount_point} - cannot use here = or ,");
+                process::exit(1);
+            }
+        }
+
+        let settings = Settings {
             check_interval: cli.check_interval,
             data_path: cli.data_path,
             plot_path: cli.plot_path,
@@ -161,12 +398,210 @@ impl From<Cli> for Settings {
             // use_web_gl: true, // TODO: add this to CLI - need to check if this works
             backup_number: cli.backup_number,
             maximum_data_file_size_bytes: (cli.maximum_data_file_size_mb * 1024.0 * 1024.0) as usize,
-            need_to_refresh_processes: !process_to_search.is_empty(),
+            skip_virtual_network_interfaces: cli.skip_virtual_network_interfaces,
+            sysfs_path: cli.sysfs_path,
+            stacked_memory_plot: cli.stacked_memory_plot,
+            // Command to run is added to processes to search only when it is started
+            need_to_refresh_processes: !process_to_search.is_empty() || !command_to_run.is_empty(),
             process_cmd_to_search: process_to_search,
+            sum_all_processes: cli.sum_all_processes,
+            process_exclusions,
+            extended_process_metrics: cli.extended_process_metrics,
+            process_pss_memory: cli.process_pss_memory,
+            mount_points_to_check: cli.mount_points_to_check,
+            tcp_local_ports: cli.tcp_local_ports,
+            users_to_check: cli.users_to_check,
+            top_users: cli.top_users,
+            top_processes: cli.top_processes,
+            top_processes_interval: cli.top_processes_interval,
+            process_events: cli.process_events || !process_events_filters.is_empty(),
+            process_events_filters,
+            command_to_run,
+            cgroups_to_check,
+            cgroup_path: cli.cgroup_path,
+            scale_to_cgroup_limits: cli.scale_to_cgroup_limits,
             start_time: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .expect("Cannot fail duration since UNIX_EPOCH")
                 .as_secs_f64(),
+        };
+
+        // Data file is still useful without plot, so only collecting and converting at once is stopped, convert mode reports it when loading data
+        let needed_axes = settings.plot_axes_count();
+        if needed_axes > MAXIMUM_PLOT_GROUPS {
+            match settings.app_mode {
+                AppMode::COLLECT_AND_CONVERT => {
+                    eprintln!(
+                        "Selected data needs {needed_axes} plot axes, but only {MAXIMUM_PLOT_GROUPS} can be shown at once, collect fewer groups of data"
+                    );
+                    process::exit(1);
+                }
+                AppMode::COLLECT => {
+                    eprintln!(
+                        "Warning: selected data needs {needed_axes} plot axes, but only {MAXIMUM_PLOT_GROUPS} can be shown at once, so data file cannot be converted to plot"
+                    );
+                }
+                AppMode::CONVERT => {}
+            }
+        }
+
+        settings
+    }
+}
+
+impl Settings {
+    // Each group uses its own pair of axes, swap rates shown together with swap usage need one more y axis
+    pub fn plot_axes_count(&self) -> usize {
+        let mut data_types = self
+            .collection_mode
+            .iter()
+            .filter_map(|e| e.to_string().parse::<DataType>().ok())
+            .collect::<Vec<_>>();
+        // Command to run is added to tracked processes only when it is started, as single process
+        let tracked_processes = self
+            .process_cmd_to_search
+            .iter()
+            .map(|e| (e.graph_name.clone(), e.all_matching))
+            .chain((!self.command_to_run.is_empty()).then(|| (self.command_to_run.join(" "), false)));
+        for (idx, (graph_name, all_matching)) in tracked_processes.enumerate() {
+            let item = (idx, graph_name);
+            data_types.extend([DataType::CUSTOM_CPU(item.clone()), DataType::CUSTOM_MEMORY(item.clone())]);
+            if !all_matching && self.extended_process_metrics {
+                data_types.push(DataType::CUSTOM_THREADS(item.clone()));
+            }
+            if !all_matching && self.process_pss_memory {
+                data_types.push(DataType::CUSTOM_PSS(item));
+            }
+        }
+        for idx in 0..self.mount_points_to_check.len() {
+            data_types.push(DataType::MOUNT_USED((idx, String::new())));
+        }
+        for (idx, cgroup) in self.cgroups_to_check.iter().enumerate() {
+            let item = (idx, cgroup.graph_name.clone());
+            data_types.extend([
+                DataType::CGROUP_MEMORY(item.clone()),
+                DataType::CGROUP_CPU(item.clone()),
+                DataType::CGROUP_THROTTLED(item),
+            ]);
+        }
+
+        let groups = data_types.iter().filter_map(DataType::group).collect::<HashSet<_>>();
+        let swap_rates_on_secondary_axis = data_types.iter().any(DataType::is_swap) && data_types.iter().any(DataType::is_swap_rate);
+        groups.len() + usize::from(swap_rates_on_secondary_axis)
+    }
+}
+
+// Items in format "NAME|VALUE", name is used as graph name
+// Value may contain |, because it is used in regex selectors
+fn split_named_item(item: &str) -> (String, String) {
+    if item.contains('=') || item.contains(',') {
+        eprintln!("{item} - cannot use here = or ,");
+        process::exit(1);
+    }
+    let Some((name, value)) = item.split_once('|') else {
+        eprintln!("{item} - should contains two parts split by |");
+        process::exit(1);
+    };
+    (name.to_string(), value.to_string())
+}
+
+// Selector is saved in header line, so it cannot contain characters used as separators
+fn parse_header_process_selector(text: &str) -> ProcessSelector {
+    if text.contains('=') || text.contains(',') {
+        eprintln!("{text} - cannot use here = or ,");
+        process::exit(1);
+    }
+    parse_process_selector(text)
+}
+
+fn parse_process_selector(text: &str) -> ProcessSelector {
+    match ProcessSelector::parse(text) {
+        Ok(selector) => selector,
+        Err(e) => {
+            eprintln!("{e:#}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_each_selector_kind() {
+        assert!(matches!(ProcessSelector::parse("pid:1234").unwrap(), ProcessSelector::Pid(1234)));
+        assert!(matches!(ProcessSelector::parse("name:nginx").unwrap(), ProcessSelector::Name(e) if e == "nginx"));
+        assert!(matches!(ProcessSelector::parse("exe:/usr/sbin/nginx").unwrap(), ProcessSelector::Exe(e) if e == Path::new("/usr/sbin/nginx")));
+        assert!(matches!(ProcessSelector::parse("user:0").unwrap(), ProcessSelector::User(uid, e) if *uid == 0 && e == "0"));
+        assert!(matches!(ProcessSelector::parse("cwd:/srv/app").unwrap(), ProcessSelector::Cwd(e) if e == Path::new("/srv/app")));
+        assert!(matches!(ProcessSelector::parse("regex:worker [0-9]+").unwrap(), ProcessSelector::Regex(e) if e.as_str() == "worker [0-9]+"));
+        assert!(matches!(ProcessSelector::parse("cmd:python").unwrap(), ProcessSelector::Cmd(e) if e == "python"));
+    }
+
+    #[test]
+    fn test_parse_text_without_kind_and_with_colons() {
+        assert!(matches!(ProcessSelector::parse("python -m http.server").unwrap(), ProcessSelector::Cmd(e) if e == "python -m http.server"));
+        assert!(
+            matches!(ProcessSelector::parse("cmd:http.server 0.0.0.0:8000").unwrap(), ProcessSelector::Cmd(e) if e == "http.server 0.0.0.0:8000")
+        );
+        assert_eq!(ProcessSelector::parse("cmd:a:b").unwrap().to_string(), "cmd:a:b");
+    }
+
+    #[test]
+    fn test_parse_invalid_selectors() {
+        for text in [
+            "http.server 0.0.0.0:8000",
+            "nmae:nginx",
+            "pid:abc",
+            "pid:",
+            "regex:worker [0-9",
+            "user:user_which_not_exists_123",
+        ] {
+            assert!(ProcessSelector::parse(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_matches_values() {
+        let exe = Path::new("/usr/bin/python3");
+        let cwd = Path::new("/srv/app");
+        let cmd = "python3 -m http.server 8000";
+        let matches = |text: &str| {
+            ProcessSelector::parse(text)
+                .unwrap()
+                .matches_values(42, "python3", Some(exe), Some(1000), Some(cwd), cmd)
+        };
+
+        for text in [
+            "pid:42",
+            "name:python3",
+            "exe:/usr/bin/python3",
+            "user:1000",
+            "cwd:/srv/app",
+            "regex:http\\.server [0-9]+",
+            "http.server",
+        ] {
+            assert!(matches(text), "{text}");
+        }
+        for text in [
+            "pid:43",
+            "name:python",
+            "exe:/usr/bin/python",
+            "user:1001",
+            "cwd:/srv",
+            "regex:^http",
+            "cmd:8001",
+        ] {
+            assert!(!matches(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_matches_values_with_unknown_exe_user_and_cwd() {
+        for text in ["exe:/usr/bin/python3", "user:1000", "cwd:/srv/app"] {
+            let selector = ProcessSelector::parse(text).unwrap();
+            assert!(!selector.matches_values(42, "python3", None, None, None, "python3"), "{text}");
         }
     }
 }
@@ -5,7 +5,8 @@ use std::time::Instant;
 use anyhow::{Context, Error};
 use chrono::{DateTime, Utc};
 use log::info;
-use plotly::common::Title;
+use plotly::color::NamedColor;
+use plotly::common::{AxisSide, Marker, MarkerSymbol, Mode, Title};
 use plotly::layout::themes::PLOTLY_DARK;
 use plotly::layout::{Axis, GridPattern, Layout, LayoutGrid};
 use plotly::{Plot, Scatter};
@@ -13,8 +14,11 @@ use regex::Regex;
 use time::UtcOffset;
 
 use crate::csv_file_loader::load_csv_results;
-use crate::enums::{DataType, GeneralInfoGroup};
-use crate::model::{CollectedItemModels, Settings};
+use crate::enums::{DataType, GeneralInfoGroup, ProcessEventKind};
+use crate::model::{CollectedItemModels, ProcessEventEntry, Settings, TopProcessEntry, TopProcessesEntry};
+
+// Plotly library supports only 8 pairs of axes
+pub const MAXIMUM_PLOT_GROUPS: usize = 8;
 
 pub fn load_results_and_save_plot(settings: &Settings) -> Result<(), Error> {
     let time_start = Instant::now();
@@ -40,11 +44,13 @@ pub fn save_plot_into_file(loaded_results: &CollectedItemModels, settings: &Sett
         Err(_) => 0,
     };
 
+    let seconds_to_date =
+        |seconds: f64| DateTime::from_timestamp_millis(((seconds + loaded_results.start_time) * 1000.0) as i64 + timezone_millis_offset);
     let dates = loaded_results.collected_data[&DataType::SECONDS_SINCE_START]
         .iter()
         .map(|str_time| {
             if let Ok(time) = str_time.parse::<f64>() {
-                DateTime::from_timestamp_millis(((time + loaded_results.start_time) * 1000.0) as i64 + timezone_millis_offset)
+                seconds_to_date(time)
             } else {
                 None
             }
@@ -54,35 +60,38 @@ pub fn save_plot_into_file(loaded_results: &CollectedItemModels, settings: &Sett
 
     let mut plot = Plot::new();
 
-    let (layout, layout_info) = create_plot_layout(loaded_results, settings);
+    let (layout, layout_info, swap_rates_axis) = create_plot_layout(loaded_results, settings)?;
     plot.set_layout(layout);
 
-    if loaded_results.collected_groups.contains(&GeneralInfoGroup::MEMORY) {
-        create_memory_plot(
-            &mut plot,
-            &dates,
-            loaded_results,
-            settings,
-            *layout_info.get(&GeneralInfoGroup::MEMORY).unwrap(),
-        );
-    }
-    if loaded_results.collected_groups.contains(&GeneralInfoGroup::CPU) {
-        create_cpu_plot(
-            &mut plot,
-            &dates,
-            loaded_results,
-            settings,
-            *layout_info.get(&GeneralInfoGroup::CPU).unwrap(),
-        );
-    }
-    if loaded_results.collected_groups.contains(&GeneralInfoGroup::SWAP) {
-        create_swap_plot(
-            &mut plot,
-            &dates,
-            loaded_results,
-            settings,
-            *layout_info.get(&GeneralInfoGroup::SWAP).unwrap(),
-        );
+    for group in &loaded_results.collected_groups {
+        let Some(&i) = layout_info.get(group) else {
+            continue; // Group was not added into layout
+        };
+        match group {
+            GeneralInfoGroup::MEMORY => create_memory_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::CPU => create_cpu_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::CPU_FREQUENCY => create_cpu_frequency_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::SWAP => create_swap_plot(&mut plot, &dates, loaded_results, settings, i, swap_rates_axis),
+            GeneralInfoGroup::VIRTUAL_MEMORY => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_virtual_memory, i),
+            GeneralInfoGroup::DISK => create_disk_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::NETWORK => create_network_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::PRESSURE => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_pressure, i),
+            GeneralInfoGroup::TEMPERATURE => create_temperature_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::POWER => create_power_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::DISK_SPACE => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_disk_space, i),
+            GeneralInfoGroup::KERNEL_ACTIVITY => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_kernel_activity, i),
+            GeneralInfoGroup::TASKS => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_tasks, i),
+            GeneralInfoGroup::DESCRIPTORS => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_descriptors, i),
+            GeneralInfoGroup::TCP_STATES => create_tcp_states_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::PROCESS_DETAILS => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_process_details, i),
+            GeneralInfoGroup::PROCESS_MEMORY => create_process_memory_plot(&mut plot, &dates, loaded_results, settings, i),
+            GeneralInfoGroup::CGROUP => create_simple_group_plot(&mut plot, &dates, loaded_results, DataType::is_cgroup, i),
+        }
+    }
+
+    // Events are shown on first plot, to not cover any values on other plots
+    if let Some(&i) = layout_info.values().min() {
+        add_process_events_traces(&mut plot, &loaded_results.process_events, seconds_to_date, i);
     }
 
     // Only replace when using dark theme
@@ -91,7 +100,7 @@ pub fn save_plot_into_file(loaded_results: &CollectedItemModels, settings: &Sett
         html = html.replace("<head>", "<head><style>body {background-color: #111111;color: white;}</style>");
     }
 
-    let notes = [
+    let mut notes = vec![
         format!("Cpu count: {}", loaded_results.cpu_core_count),
         format!("Check interval: {}s", loaded_results.check_interval),
         // format!("Start time: {}", loaded_results.start_time),
@@ -104,6 +113,31 @@ pub fn save_plot_into_file(loaded_results: &CollectedItemModels, settings: &Sett
             humansize::format_size((loaded_results.swap_total * 1024.0 * 1024.0) as u64, humansize::BINARY)
         ),
     ];
+    if let Some(memory_limit) = loaded_results.effective_memory_limit {
+        notes.push(format!(
+            "Cgroup memory limit: {}",
+            humansize::format_size((memory_limit * 1024.0 * 1024.0) as u64, humansize::BINARY)
+        ));
+    }
+    if let Some(cpu_limit) = loaded_results.effective_cpu_limit {
+        notes.push(format!("Cgroup cpu limit: {cpu_limit} cores"));
+    }
+    for process_selector in &loaded_results.process_selectors {
+        notes.push(format!("Tracked process: {process_selector}"));
+    }
+    if !loaded_results.process_exclusions.is_empty() {
+        notes.push(format!("Excluded processes: {}", loaded_results.process_exclusions.join(", ")));
+    }
+    if let Some(command) = &loaded_results.command {
+        notes.push(format!("Command: {command}"));
+        match (&loaded_results.command_exit_status, loaded_results.command_wall_time_seconds) {
+            (Some(exit_status), Some(wall_time)) => notes.push(format!("Exit status: {exit_status}, wall time: {wall_time:.2}s")),
+            _ => notes.push("Command was still running when collecting ended".to_string()),
+        }
+    }
+    if !loaded_results.process_events_filters.is_empty() {
+        notes.push(format!("Process events only of: {}", loaded_results.process_events_filters.join(", ")));
+    }
 
     #[allow(clippy::format_collect)]
     let notes = notes
@@ -120,20 +154,29 @@ pub fn save_plot_into_file(loaded_results: &CollectedItemModels, settings: &Sett
     Ok(())
 }
 
-pub fn create_plot_layout(loaded_results: &CollectedItemModels, settings: &Settings) -> (Layout, HashMap<GeneralInfoGroup, u32>) {
-    let contains_memory_group = loaded_results.collected_groups.contains(&GeneralInfoGroup::MEMORY);
-    let contains_cpu_group = loaded_results.collected_groups.contains(&GeneralInfoGroup::CPU);
-    let contains_swap_group = loaded_results.collected_groups.contains(&GeneralInfoGroup::SWAP);
+type PlotLayout = (Layout, HashMap<GeneralInfoGroup, u32>, Option<u32>);
+
+// Returns also index of secondary axis used by swap rates, if they are shown together with swap usage
+pub fn create_plot_layout(loaded_results: &CollectedItemModels, settings: &Settings) -> Result<PlotLayout, Error> {
+    let has_swap_usage = loaded_results.collected_data.keys().any(DataType::is_swap);
+    let has_swap_rates = loaded_results.collected_data.keys().any(DataType::is_swap_rate);
+    let swap_rates_on_secondary_axis = has_swap_usage && has_swap_rates;
+
+    // Secondary axis uses one of available axes, so one group less can be shown
+    let maximum_plot_groups = MAXIMUM_PLOT_GROUPS - usize::from(swap_rates_on_secondary_axis);
+    let shown_groups = &loaded_results.collected_groups;
+    if shown_groups.len() > maximum_plot_groups {
+        return Err(Error::msg(format!(
+            "Only {maximum_plot_groups} groups can be shown at once, but data file contains {} - {:?}",
+            shown_groups.len(),
+            shown_groups
+        )));
+    }
 
     let mut layout = Layout::new()
         .width(settings.plot_width as usize)
         .height(settings.plot_height as usize)
-        .grid(
-            LayoutGrid::new()
-                .rows(contains_cpu_group as usize + contains_memory_group as usize + contains_swap_group as usize)
-                .columns(1)
-                .pattern(GridPattern::Independent),
-        );
+        .grid(LayoutGrid::new().rows(shown_groups.len()).columns(1).pattern(GridPattern::Independent));
 
     if !settings.white_plot_mode {
         layout = layout.template(&*PLOTLY_DARK);
@@ -143,49 +186,235 @@ pub fn create_plot_layout(loaded_results: &CollectedItemModels, settings: &Setti
     let x_axis = Axis::new().title(Title::with_text("Time"));
 
     let mut current_axis_idx = 1;
-    if contains_memory_group {
-        layout_idx_info.insert(GeneralInfoGroup::MEMORY, current_axis_idx);
-        let y_axis = Axis::new()
-            .range(vec![0, loaded_results.memory_total.ceil() as usize])
-            .title(Title::with_text("Memory Usage[MB]"));
+    for group in shown_groups {
+        layout_idx_info.insert(*group, current_axis_idx);
+        let y_axis = match group {
+            GeneralInfoGroup::MEMORY => Axis::new()
+                .range(vec![0, get_memory_limit(loaded_results, settings).ceil() as usize])
+                .title(Title::with_text("Memory Usage[MB]")),
+            GeneralInfoGroup::CPU => {
+                if get_cpu_usage_scale(loaded_results, settings).is_some() {
+                    Axis::new()
+                        .range(vec![-1, 100])
+                        .title(Title::with_text("CPU Usage[%, cgroups % of limit]"))
+                } else {
+                    Axis::new().range(vec![-1, 100]).title(Title::with_text("CPU Usage[%]"))
+                }
+            }
+            GeneralInfoGroup::CPU_FREQUENCY => Axis::new().title(Title::with_text("CPU Frequency[MHz]")),
+            GeneralInfoGroup::SWAP => {
+                if has_swap_usage {
+                    Axis::new()
+                        .range(vec![0, loaded_results.swap_total.ceil() as usize])
+                        .title(Title::with_text("Swap Usage[MB]"))
+                } else {
+                    Axis::new().title(Title::with_text("Swap[pages/s]"))
+                }
+            }
+            GeneralInfoGroup::VIRTUAL_MEMORY => Axis::new().title(Title::with_text("Virtual memory[events/s, count]")),
+            GeneralInfoGroup::DISK => Axis::new().title(Title::with_text("Disk I/O[MB/s, IOPS, %]")),
+            GeneralInfoGroup::NETWORK => Axis::new().title(Title::with_text("Network[MB/s, packets/s]")),
+            GeneralInfoGroup::PRESSURE => Axis::new().title(Title::with_text("Load average / Pressure[%]")),
+            GeneralInfoGroup::TEMPERATURE => Axis::new().title(Title::with_text("Temperature[°C]")),
+            GeneralInfoGroup::POWER => Axis::new().title(Title::with_text("Power[%, V, A, W]")),
+            GeneralInfoGroup::DISK_SPACE => Axis::new().title(Title::with_text("Disk space[MB, inodes %]")),
+            GeneralInfoGroup::KERNEL_ACTIVITY => Axis::new().title(Title::with_text("Kernel activity[events/s, count]")),
+            GeneralInfoGroup::TASKS => Axis::new().title(Title::with_text("Tasks")),
+            GeneralInfoGroup::DESCRIPTORS => Axis::new().title(Title::with_text("Files and sockets[count, MB]")),
+            GeneralInfoGroup::TCP_STATES => {
+                if loaded_results.tcp_local_ports.is_empty() {
+                    Axis::new().title(Title::with_text("TCP connections"))
+                } else {
+                    Axis::new().title(Title::with_text(format!(
+                        "TCP connections[ports {}]",
+                        loaded_results.tcp_local_ports.join(", ")
+                    )))
+                }
+            }
+            GeneralInfoGroup::PROCESS_DETAILS => Axis::new().title(Title::with_text("Processes[count, /s, MB, MB/s]")),
+            GeneralInfoGroup::PROCESS_MEMORY => Axis::new().title(Title::with_text("Process memory[MB]")),
+            GeneralInfoGroup::CGROUP => Axis::new().title(Title::with_text("Cgroups[MB/s, throttled periods/s]")),
+        };
 
-        layout = set_axes_into_layout(&mut current_axis_idx, layout, x_axis.clone(), y_axis);
+        layout = set_axes_into_layout(&mut current_axis_idx, layout, x_axis.clone(), y_axis)?;
     }
-    if contains_cpu_group {
-        layout_idx_info.insert(GeneralInfoGroup::CPU, current_axis_idx);
-        let y_axis = Axis::new().range(vec![-1, 100]).title(Title::with_text("CPU Usage[%]"));
 
-        layout = set_axes_into_layout(&mut current_axis_idx, layout, x_axis.clone(), y_axis);
-    }
-    if contains_swap_group {
-        layout_idx_info.insert(GeneralInfoGroup::SWAP, current_axis_idx);
+    // Secondary axis is placed after all axes used by groups, so it doesn't break grid
+    let mut swap_rates_axis = None;
+    if let (true, Some(&swap_idx)) = (swap_rates_on_secondary_axis, layout_idx_info.get(&GeneralInfoGroup::SWAP)) {
         let y_axis = Axis::new()
-            .range(vec![0, loaded_results.swap_total.ceil() as usize])
-            .title(Title::with_text("Swap Usage[MB]"));
+            .title(Title::with_text("Swap in/out[pages/s]"))
+            .overlaying(format!("y{swap_idx}"))
+            .anchor(format!("x{swap_idx}"))
+            .side(AxisSide::Right);
+        swap_rates_axis = Some(current_axis_idx);
+        layout = set_secondary_y_axis_into_layout(&mut current_axis_idx, layout, y_axis)?;
+    }
+
+    Ok((layout, layout_idx_info, swap_rates_axis))
+}
 
-        layout = set_axes_into_layout(&mut current_axis_idx, layout, x_axis.clone(), y_axis);
+// Memory limit of cgroup is used only when it is smaller than total memory, because usually limit is set to some huge value
+fn get_memory_limit(loaded_results: &CollectedItemModels, settings: &Settings) -> f64 {
+    match loaded_results.effective_memory_limit {
+        Some(memory_limit) if settings.scale_to_cgroup_limits => memory_limit.min(loaded_results.memory_total),
+        _ => loaded_results.memory_total,
     }
+}
 
-    (layout, layout_idx_info)
+// Cpu usage is collected as percentage of all cores, so it needs to be multiplied to show it as percentage of cpu limit
+fn get_cpu_usage_scale(loaded_results: &CollectedItemModels, settings: &Settings) -> Option<f64> {
+    match loaded_results.effective_cpu_limit {
+        Some(cpu_limit) if settings.scale_to_cgroup_limits && cpu_limit > 0.0 && cpu_limit < loaded_results.cpu_core_count as f64 => {
+            Some(loaded_results.cpu_core_count as f64 / cpu_limit)
+        }
+        _ => None,
+    }
 }
 
-pub fn create_memory_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
-    for (data_type, data) in &loaded_results.collected_data {
+pub fn create_memory_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, settings: &Settings, i: u32) {
+    let mut stacked_parts = Vec::new();
+    for (data_type, data) in loaded_results.ordered_collected_data() {
         if !data_type.is_memory() {
             continue;
         }
+        if data_type.is_per_pid() || data_type.is_per_user() {
+            add_per_key_traces(plot, dates, data, data_type, i);
+            continue;
+        }
 
+        let mut trace = Scatter::new(dates.to_owned(), data.clone())
+            // .web_gl_mode(settings.use_web_gl)
+            .name(data_type.pretty_print())
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"));
+        if *data_type == DataType::MEMORY_USED && !loaded_results.top_processes.is_empty() {
+            trace = trace.hover_text_array(get_top_processes_hover_texts(loaded_results, "Top memory usage", " MB", |e| {
+                &e.top_memory
+            }));
+        }
+        if settings.stacked_memory_plot && data_type.is_memory_stacked_part() {
+            trace = trace.stack_group("memory");
+            stacked_parts.push(data);
+        }
+        plot.add_trace(trace);
+    }
+
+    // Rest of memory e.g. page tables or kernel stack, is added to stacked parts, so they sum up to total memory
+    if !stacked_parts.is_empty() {
+        let other_memory = (0..dates.len())
+            .map(|idx| {
+                let used_by_parts = stacked_parts
+                    .iter()
+                    .filter_map(|data| data.get(idx).and_then(|e| e.parse::<f64>().ok()))
+                    .filter(|e| *e >= 0.0)
+                    .sum::<f64>();
+                format!("{:.2}", (loaded_results.memory_total - used_by_parts).max(0.0))
+            })
+            .collect::<Vec<_>>();
+        let trace = Scatter::new(dates.to_owned(), other_memory)
+            .name("Memory other")
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"))
+            .stack_group("memory");
+        plot.add_trace(trace);
+    }
+}
+pub fn create_swap_plot(
+    plot: &mut Plot,
+    dates: &[DateTime<Utc>],
+    loaded_results: &CollectedItemModels,
+    _settings: &Settings,
+    i: u32,
+    swap_rates_axis: Option<u32>,
+) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_swap() && !data_type.is_swap_rate() {
+            continue;
+        }
+        let y_axis_idx = match swap_rates_axis {
+            Some(swap_rates_axis) if data_type.is_swap_rate() => swap_rates_axis,
+            _ => i,
+        };
         let trace = Scatter::new(dates.to_owned(), data.clone())
             // .web_gl_mode(settings.use_web_gl)
             .name(data_type.pretty_print())
+            .y_axis(format!("y{y_axis_idx}"))
+            .x_axis(format!("x{i}"));
+        plot.add_trace(trace);
+    }
+}
+
+pub fn create_cpu_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, settings: &Settings, i: u32) {
+    let cpu_usage_scale = get_cpu_usage_scale(loaded_results, settings);
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        // Per core data are handled differently below
+        if !data_type.is_cpu() || data_type.is_per_item() {
+            continue;
+        }
+        if data_type.is_per_pid() || data_type.is_per_user() {
+            add_per_key_traces(plot, dates, data, data_type, i);
+            continue;
+        }
+        // Only cgroups are limited by cpu quota, total, per core and process usage is still share of all host cores
+        let (data, name) = match cpu_usage_scale {
+            Some(scale) if matches!(data_type, DataType::CGROUP_CPU(_)) => {
+                (scale_values(data, scale), format!("{}[% of limit]", data_type.pretty_print()))
+            }
+            _ => (data.clone(), data_type.pretty_print()),
+        };
+        let mut trace = Scatter::new(dates.to_owned(), data)
+            // .web_gl_mode(settings.use_web_gl)
+            .name(name)
             .y_axis(format!("y{i}"))
             .x_axis(format!("x{i}"));
+        if *data_type == DataType::CPU_USAGE_TOTAL && !loaded_results.top_processes.is_empty() {
+            trace = trace.hover_text_array(get_top_processes_hover_texts(loaded_results, "Top cpu usage", "%", |e| &e.top_cpu));
+        }
+        // Cpu states together sum up to 100%, so stacked area shows nicely which state takes most time
+        if data_type.is_cpu_state() {
+            trace = trace.stack_group("cpu_states");
+        }
         plot.add_trace(trace);
     }
+
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_cpu_state_per_core() {
+            continue;
+        }
+        let names = loaded_results
+            .get_item_names(data_type)
+            .iter()
+            .map(|core| format!("{core} - {}", data_type.pretty_print()))
+            .collect::<Vec<_>>();
+        add_per_item_traces(plot, dates, data, &names, i);
+    }
+
+    // CPU per core uses different way of collecting data
+    if let Some(multiple_cpu_data) = loaded_results.collected_data.get(&DataType::CPU_USAGE_PER_CORE) {
+        let names = loaded_results.get_item_names(&DataType::CPU_USAGE_PER_CORE);
+        add_per_item_traces(plot, dates, multiple_cpu_data, &names, i);
+    }
 }
-pub fn create_swap_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
-    for (data_type, data) in &loaded_results.collected_data {
-        if !data_type.is_swap() {
+
+pub fn create_cpu_frequency_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    // Frequency per core uses the same way of collecting data as CPU usage per core
+    if let Some(multiple_cpu_data) = loaded_results.collected_data.get(&DataType::CPU_FREQUENCY_PER_CORE) {
+        let names = loaded_results.get_item_names(&DataType::CPU_FREQUENCY_PER_CORE);
+        add_per_item_traces(plot, dates, multiple_cpu_data, &names, i);
+    }
+}
+
+// Groups in which every data type is shown as single line, without any special handling
+pub fn create_simple_group_plot(
+    plot: &mut Plot,
+    dates: &[DateTime<Utc>],
+    loaded_results: &CollectedItemModels,
+    predicate: fn(&DataType) -> bool,
+    i: u32,
+) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !predicate(data_type) {
             continue;
         }
         let trace = Scatter::new(dates.to_owned(), data.clone())
@@ -197,41 +426,253 @@ pub fn create_swap_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results
     }
 }
 
-pub fn create_cpu_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
-    for (data_type, data) in &loaded_results.collected_data {
-        // CPU_USAGE_PER_CORE is handled differently below
-        if !data_type.is_cpu() || data_type == &DataType::CPU_USAGE_PER_CORE {
+pub fn create_tcp_states_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_tcp_states() {
             continue;
         }
+        // Every connection is in exactly one state, so stacked area shows total number of connections
         let trace = Scatter::new(dates.to_owned(), data.clone())
+            // .web_gl_mode(settings.use_web_gl)
+            .name(data_type.pretty_print())
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"))
+            .stack_group("tcp_states");
+        plot.add_trace(trace);
+    }
+}
+
+pub fn create_process_memory_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_process_memory() {
+            continue;
+        }
+        let mut trace = Scatter::new(dates.to_owned(), data.clone())
             // .web_gl_mode(settings.use_web_gl)
             .name(data_type.pretty_print())
             .y_axis(format!("y{i}"))
             .x_axis(format!("x{i}"));
+        // Parts of pss with swap are stacked separately for each process, total pss is shown as line
+        match data_type {
+            DataType::CUSTOM_PSS_ANON((idx, _))
+            | DataType::CUSTOM_PSS_FILE((idx, _))
+            | DataType::CUSTOM_PSS_SHMEM((idx, _))
+            | DataType::CUSTOM_SWAP_PSS((idx, _)) => {
+                trace = trace.stack_group(format!("process_{idx}"));
+            }
+            _ => {}
+        }
         plot.add_trace(trace);
     }
+}
 
-    // CPU per core uses different way of collecting data
-    if let Some(multiple_cpu_data) = loaded_results.collected_data.get(&DataType::CPU_USAGE_PER_CORE) {
-        for (idx, single_cpu_data) in multiple_cpu_data.iter().enumerate() {
-            let single_cpu_data = single_cpu_data.split(';').map(ToString::to_string).collect::<Vec<String>>();
-            let trace = Scatter::new(dates.to_owned(), single_cpu_data)
-                // .web_gl_mode(settings.use_web_gl)
-                .name(format!("Core {idx}"))
-                .y_axis(format!("y{i}"))
-                .x_axis(format!("x{i}"));
-            plot.add_trace(trace);
+pub fn create_temperature_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    if let Some(multiple_temperature_data) = loaded_results.collected_data.get(&DataType::TEMPERATURE) {
+        let names = loaded_results.get_item_names(&DataType::TEMPERATURE);
+        add_per_item_traces(plot, dates, multiple_temperature_data, &names, i);
+    }
+}
+
+pub fn create_power_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_power() {
+            continue;
         }
+        let names = loaded_results
+            .get_item_names(data_type)
+            .iter()
+            .map(|supply| format!("{supply} - {}", data_type.pretty_print()))
+            .collect::<Vec<_>>();
+        add_per_item_traces(plot, dates, data, &names, i);
     }
 }
 
-fn set_axes_into_layout(idx: &mut u32, layout: Layout, x_axis: Axis, y_axis: Axis) -> Layout {
+pub fn create_disk_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_disk() {
+            continue;
+        }
+        let names = loaded_results
+            .get_item_names(data_type)
+            .iter()
+            .map(|device| format!("{} - {device}", data_type.pretty_print()))
+            .collect::<Vec<_>>();
+        add_per_item_traces(plot, dates, data, &names, i);
+    }
+}
+
+pub fn create_network_plot(plot: &mut Plot, dates: &[DateTime<Utc>], loaded_results: &CollectedItemModels, _settings: &Settings, i: u32) {
+    for (data_type, data) in loaded_results.ordered_collected_data() {
+        if !data_type.is_network() {
+            continue;
+        }
+        let names = loaded_results
+            .get_item_names(data_type)
+            .iter()
+            .map(|interface| format!("{} - {interface}", data_type.pretty_print()))
+            .collect::<Vec<_>>();
+        add_per_item_traces(plot, dates, data, &names, i);
+    }
+}
+
+// Not available values(-1) are kept as they are
+fn scale_values(data: &[String], scale: f64) -> Vec<String> {
+    data.iter()
+        .map(|e| match e.parse::<f64>() {
+            Ok(value) if value >= 0.0 => format!("{:.2}", value * scale),
+            _ => e.clone(),
+        })
+        .collect()
+}
+
+// Every item of per item data is stored as values split by ;
+fn add_per_item_traces(plot: &mut Plot, dates: &[DateTime<Utc>], multiple_data: &[String], names: &[String], i: u32) {
+    for (single_data, name) in multiple_data.iter().zip(names) {
+        let single_data = single_data.split(';').map(ToString::to_string).collect::<Vec<String>>();
+        let trace = Scatter::new(dates.to_owned(), single_data)
+            // .web_gl_mode(settings.use_web_gl)
+            .name(name)
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"));
+        plot.add_trace(trace);
+    }
+}
+
+// Every process or user is shown as separate trace, which contains gaps when process was not running or user was not in top users
+fn add_per_key_traces(plot: &mut Plot, dates: &[DateTime<Utc>], data: &[String], data_type: &DataType, i: u32) {
+    // Traces are shown in order of first appearance
+    let mut keys: Vec<&str> = Vec::new();
+    let mut per_key_data: HashMap<&str, Vec<String>> = HashMap::new();
+    for (idx, items) in data.iter().enumerate() {
+        for item in items.split(';') {
+            let Some((key, value)) = item.split_once(':') else {
+                continue; // No process was running
+            };
+            per_key_data.entry(key).or_insert_with(|| {
+                keys.push(key);
+                vec![String::new(); data.len()]
+            })[idx] = value.to_string();
+        }
+    }
+
+    for key in keys {
+        let name = if data_type.is_per_pid() {
+            format!("{} - pid {key}", data_type.pretty_print())
+        } else {
+            format!("{} {key}", data_type.pretty_print())
+        };
+        let trace = Scatter::new(dates.to_owned(), per_key_data.remove(key).unwrap_or_default())
+            // .web_gl_mode(settings.use_web_gl)
+            .name(name)
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"));
+        plot.add_trace(trace);
+    }
+}
+
+// Started and exited processes are shown as separate traces, so they can be hidden independently
+fn add_process_events_traces(plot: &mut Plot, process_events: &[ProcessEventEntry], seconds_to_date: impl Fn(f64) -> Option<DateTime<Utc>>, i: u32) {
+    for (kind, symbol, color) in [
+        (ProcessEventKind::STARTED, MarkerSymbol::TriangleUp, NamedColor::LimeGreen),
+        (ProcessEventKind::EXITED, MarkerSymbol::TriangleDown, NamedColor::Red),
+    ] {
+        let events = process_events
+            .iter()
+            .filter(|e| e.kind == kind)
+            .filter_map(|e| Some((seconds_to_date(e.seconds_since_start)?, e)))
+            .collect::<Vec<_>>();
+        if events.is_empty() {
+            continue;
+        }
+        let hover_texts = events
+            .iter()
+            .map(|(_, e)| match e.kind {
+                ProcessEventKind::STARTED => format!("Started {} ({}), parent {}<br>{}", e.name, e.pid, e.ppid, e.cmd),
+                ProcessEventKind::EXITED => format!(
+                    "Exited {} ({}) after {}s, parent {}<br>{}",
+                    e.name, e.pid, e.lifetime_seconds, e.ppid, e.cmd
+                ),
+            })
+            .collect::<Vec<_>>();
+        let name = match kind {
+            ProcessEventKind::STARTED => "Process started",
+            ProcessEventKind::EXITED => "Process exited",
+        };
+        let trace = Scatter::new(events.iter().map(|(date, _)| *date).collect(), vec![0; events.len()])
+            .mode(Mode::Markers)
+            .marker(Marker::new().symbol(symbol).size(10).color(color))
+            .hover_text_array(hover_texts)
+            .name(name)
+            .y_axis(format!("y{i}"))
+            .x_axis(format!("x{i}"));
+        plot.add_trace(trace);
+    }
+}
+
+// Top processes may be saved only every few ticks, with cpu usage averaged since previous save,
+// so every point shows processes from first save done at or after it
+fn get_top_processes_hover_texts(
+    loaded_results: &CollectedItemModels,
+    title: &str,
+    unit: &str,
+    processes: impl Fn(&TopProcessesEntry) -> &[TopProcessEntry],
+) -> Vec<String> {
+    let top_processes = &loaded_results.top_processes;
+    loaded_results.collected_data[&DataType::SECONDS_SINCE_START]
+        .iter()
+        .map(|seconds| {
+            let Ok(seconds) = seconds.parse::<f64>() else {
+                return String::new();
+            };
+            let idx = top_processes.partition_point(|e| e.seconds_since_start < seconds);
+            let Some(entry) = top_processes.get(idx) else {
+                return String::new();
+            };
+            let lines = processes(entry)
+                .iter()
+                .map(|e| format!("{} ({}) - {}{unit}", e.name, e.pid, e.value))
+                .collect::<Vec<_>>();
+            format!("{title}:<br>{}", lines.join("<br>"))
+        })
+        .collect()
+}
+
+fn set_axes_into_layout(idx: &mut u32, layout: Layout, x_axis: Axis, y_axis: Axis) -> Result<Layout, Error> {
     let new_layout = match idx {
         1 => layout.x_axis(x_axis).y_axis(y_axis),
         2 => layout.x_axis2(x_axis).y_axis2(y_axis),
         3 => layout.x_axis3(x_axis).y_axis3(y_axis),
-        _ => panic!(),
+        4 => layout.x_axis4(x_axis).y_axis4(y_axis),
+        5 => layout.x_axis5(x_axis).y_axis5(y_axis),
+        6 => layout.x_axis6(x_axis).y_axis6(y_axis),
+        7 => layout.x_axis7(x_axis).y_axis7(y_axis),
+        8 => layout.x_axis8(x_axis).y_axis8(y_axis),
+        _ => {
+            return Err(Error::msg(format!(
+                "Plotly supports only {MAXIMUM_PLOT_GROUPS} axes, cannot set axis {idx}"
+            )))
+        }
+    };
+    *idx += 1;
+    Ok(new_layout)
+}
+
+fn set_secondary_y_axis_into_layout(idx: &mut u32, layout: Layout, y_axis: Axis) -> Result<Layout, Error> {
+    let new_layout = match idx {
+        1 => layout.y_axis(y_axis),
+        2 => layout.y_axis2(y_axis),
+        3 => layout.y_axis3(y_axis),
+        4 => layout.y_axis4(y_axis),
+        5 => layout.y_axis5(y_axis),
+        6 => layout.y_axis6(y_axis),
+        7 => layout.y_axis7(y_axis),
+        8 => layout.y_axis8(y_axis),
+        _ => {
+            return Err(Error::msg(format!(
+                "Plotly supports only {MAXIMUM_PLOT_GROUPS} axes, cannot set axis {idx}"
+            )))
+        }
     };
     *idx += 1;
-    new_layout
+    Ok(new_layout)
 }
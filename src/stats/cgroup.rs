@@ -0,0 +1,304 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use log::warn;
+
+use crate::model::CgroupStruct;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CgroupValues {
+    pub memory_current_bytes: Option<u64>,
+    // None when there is no limit set("max" value)
+    pub memory_max_bytes: Option<u64>,
+    pub cpu_usage_percent: Option<f64>,
+    pub throttled_periods_per_second: Option<f64>,
+    pub io_read_bytes_per_second: Option<f64>,
+    pub io_write_bytes_per_second: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct CgroupSample {
+    usage_usec: Option<u64>,
+    nr_throttled: Option<u64>,
+    io_read_bytes: Option<u64>,
+    io_write_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CgroupStatsCache {
+    cgroup_paths: Vec<PathBuf>,
+    core_count: usize,
+    previous_samples: Vec<Option<CgroupSample>>,
+    // None if cgroup is not available e.g. service was stopped and its cgroup removed
+    pub values: Vec<Option<CgroupValues>>,
+}
+
+impl CgroupStatsCache {
+    pub fn new(cgroup_root: &str, cgroups: &[CgroupStruct], core_count: usize) -> Self {
+        let cgroup_paths = cgroups
+            .iter()
+            .map(|cgroup| Path::new(cgroup_root).join(cgroup.cgroup_path.trim_start_matches('/')))
+            .collect::<Vec<_>>();
+        for (cgroup, path) in cgroups.iter().zip(&cgroup_paths) {
+            if !path.join("cgroup.controllers").exists() {
+                warn!(
+                    "Cgroup \"{}\" not found in {}, values will be collected when it appears",
+                    cgroup.graph_name,
+                    path.display()
+                );
+            }
+        }
+
+        let previous_samples = cgroup_paths.iter().map(|path| read_cgroup_sample(path)).collect();
+        CgroupStatsCache {
+            values: vec![None; cgroup_paths.len()],
+            cgroup_paths,
+            core_count,
+            previous_samples,
+        }
+    }
+
+    pub fn refresh(&mut self, elapsed: Duration) {
+        let elapsed_seconds = elapsed.as_secs_f64();
+        let mut values = Vec::with_capacity(self.cgroup_paths.len());
+        for (path, previous_sample) in self.cgroup_paths.iter().zip(self.previous_samples.iter_mut()) {
+            let current_sample = read_cgroup_sample(path);
+            let cgroup_values = current_sample.map(|current| {
+                let previous = previous_sample.unwrap_or_default();
+                let rate = |current: Option<u64>, previous: Option<u64>| match (current, previous) {
+                    (Some(current), Some(previous)) if elapsed_seconds > 0.0 => Some(current.saturating_sub(previous) as f64 / elapsed_seconds),
+                    _ => None,
+                };
+                // The same as for processes, 100% means that all cores are fully used
+                let cpu_usage_percent = rate(current.usage_usec, previous.usage_usec).map(|e| e / 1_000_000.0 / self.core_count as f64 * 100.0);
+
+                CgroupValues {
+                    memory_current_bytes: read_cgroup_value(&path.join("memory.current")),
+                    memory_max_bytes: read_cgroup_value(&path.join("memory.max")),
+                    cpu_usage_percent,
+                    throttled_periods_per_second: rate(current.nr_throttled, previous.nr_throttled),
+                    io_read_bytes_per_second: rate(current.io_read_bytes, previous.io_read_bytes),
+                    io_write_bytes_per_second: rate(current.io_write_bytes, previous.io_write_bytes),
+                }
+            });
+            values.push(cgroup_values);
+            *previous_sample = current_sample;
+        }
+        self.values = values;
+    }
+}
+
+fn read_cgroup_sample(path: &Path) -> Option<CgroupSample> {
+    if !path.is_dir() {
+        return None;
+    }
+
+    let mut sample = CgroupSample::default();
+
+    // Cpu controller may be disabled, but usage_usec is always available in cgroup v2
+    if let Some(cpu_stat) = read_flat_keyed_file(&path.join("cpu.stat")) {
+        sample.usage_usec = cpu_stat.iter().find(|(key, _)| key == "usage_usec").map(|(_, value)| *value);
+        sample.nr_throttled = cpu_stat.iter().find(|(key, _)| key == "nr_throttled").map(|(_, value)| *value);
+    }
+
+    // Format - "8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0", one line per device
+    if let Ok(io_stat) = fs::read_to_string(path.join("io.stat")) {
+        let (mut read_bytes, mut write_bytes) = (0, 0);
+        for line in io_stat.lines() {
+            for (key, value) in line.split_whitespace().skip(1).filter_map(|e| e.split_once('=')) {
+                match key {
+                    "rbytes" => read_bytes += value.parse::<u64>().unwrap_or(0),
+                    "wbytes" => write_bytes += value.parse::<u64>().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+        sample.io_read_bytes = Some(read_bytes);
+        sample.io_write_bytes = Some(write_bytes);
+    }
+
+    Some(sample)
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct EffectiveLimits {
+    pub memory_bytes: Option<u64>,
+    pub cpu_cores: Option<f64>,
+}
+
+// Limits of app own cgroup, limits of parent cgroups also apply, so the lowest one is used
+// Inside container, cgroup namespace usually makes own cgroup visible as root of cgroupfs
+pub fn read_effective_limits(cgroup_root: &str) -> EffectiveLimits {
+    let own_cgroup = fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|content| content.lines().find_map(|line| line.strip_prefix("0::").map(ToString::to_string)))
+        .unwrap_or_default();
+    read_limits_up_to_root(Path::new(cgroup_root), &own_cgroup)
+}
+
+fn read_limits_up_to_root(cgroup_root: &Path, own_cgroup: &str) -> EffectiveLimits {
+    let mut limits = EffectiveLimits::default();
+    let mut current_path = cgroup_root.join(own_cgroup.trim_start_matches('/'));
+    loop {
+        if let Some(memory_max) = read_cgroup_value(&current_path.join("memory.max")) {
+            limits.memory_bytes = Some(limits.memory_bytes.map_or(memory_max, |e| e.min(memory_max)));
+        }
+        if let Some(cpu_max) = read_cpu_max(&current_path.join("cpu.max")) {
+            limits.cpu_cores = Some(limits.cpu_cores.map_or(cpu_max, |e| e.min(cpu_max)));
+        }
+        if current_path == cgroup_root || !current_path.pop() {
+            break;
+        }
+    }
+    limits
+}
+
+// Format - "200000 100000" - quota and period in microseconds, or "max 100000" when there is no limit
+fn read_cpu_max(path: &Path) -> Option<f64> {
+    let content = fs::read_to_string(path).ok()?;
+    let (quota, period) = content.trim().split_once(' ')?;
+    let quota = quota.parse::<u64>().ok()?;
+    let period = period.parse::<u64>().ok()?;
+    if period == 0 {
+        return None;
+    }
+    Some(quota as f64 / period as f64)
+}
+
+// Reads single value files like memory.current, "max" means that there is no limit
+fn read_cgroup_value(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse::<u64>().ok()
+}
+
+// Format - "usage_usec 123456", one pair per line
+fn read_flat_keyed_file(path: &Path) -> Option<Vec<(String, u64)>> {
+    let content = fs::read_to_string(path).ok()?;
+    Some(
+        content
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                Some((key.to_string(), value.trim().parse::<u64>().ok()?))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_cpu_max_with_quota_and_period() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("cpu.max", "150000 100000\n");
+        assert_eq!(read_cpu_max(&test_dir.path.join("cpu.max")), Some(1.5));
+    }
+
+    #[test]
+    fn test_cpu_max_without_limit() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("cpu.max", "max 100000\n");
+        assert_eq!(read_cpu_max(&test_dir.path.join("cpu.max")), None);
+    }
+
+    #[test]
+    fn test_cpu_max_broken_missing_and_unreadable() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("zero_period", "100000 0\n");
+        test_dir.write("no_period", "100000\n");
+        test_dir.make_unreadable("unreadable");
+        for file in ["zero_period", "no_period", "unreadable", "missing"] {
+            assert_eq!(read_cpu_max(&test_dir.path.join(file)), None, "{file}");
+        }
+    }
+
+    #[test]
+    fn test_effective_limits_are_lowest_of_own_and_parent_cgroups() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("memory.max", "max\n");
+        test_dir.write("cpu.max", "max 100000\n");
+        test_dir.write("system.slice/memory.max", "1073741824\n");
+        test_dir.write("system.slice/cpu.max", "400000 100000\n");
+        test_dir.write("system.slice/app.service/memory.max", "2147483648\n");
+        test_dir.write("system.slice/app.service/cpu.max", "50000 100000\n");
+
+        let limits = read_limits_up_to_root(&test_dir.path, "/system.slice/app.service");
+        assert_eq!(limits.memory_bytes, Some(1073741824));
+        assert_eq!(limits.cpu_cores, Some(0.5));
+    }
+
+    #[test]
+    fn test_effective_limits_without_limits() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("app.service/memory.max", "max\n");
+        test_dir.write("app.service/cpu.max", "max 100000\n");
+        test_dir.make_unreadable("memory.max");
+
+        let limits = read_limits_up_to_root(&test_dir.path, "/app.service");
+        assert_eq!(limits.memory_bytes, None);
+        assert_eq!(limits.cpu_cores, None);
+    }
+
+    #[test]
+    fn test_effective_limits_ignore_cgroups_above_root() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("memory.max", "1024\n");
+        test_dir.write("container/app.service/memory.max", "max\n");
+
+        let limits = read_limits_up_to_root(&test_dir.path.join("container"), "/app.service");
+        assert_eq!(limits.memory_bytes, None);
+    }
+
+    #[test]
+    fn test_refresh_computes_rates_from_stat_files() {
+        let test_dir = TestDir::new("cgroup");
+        test_dir.write("app.service/cpu.stat", "usage_usec 1000000\nuser_usec 600000\nnr_throttled 10\n");
+        test_dir.write("app.service/io.stat", "8:0 rbytes=1000 wbytes=2000 rios=1 wios=2\n");
+        test_dir.write("app.service/memory.current", "4096\n");
+        test_dir.write("app.service/memory.max", "max\n");
+        let cgroups = [CgroupStruct {
+            graph_name: "app".to_string(),
+            cgroup_path: "/app.service".to_string(),
+        }];
+        let mut cache = CgroupStatsCache::new(test_dir.path_str(), &cgroups, 2);
+
+        test_dir.write("app.service/cpu.stat", "usage_usec 3000000\nuser_usec 600000\nnr_throttled 14\n");
+        test_dir.write(
+            "app.service/io.stat",
+            "8:0 rbytes=5000 wbytes=2000 rios=2 wios=2\n8:16 rbytes=2000 wbytes=6000 rios=1 wios=1\n",
+        );
+        cache.refresh(Duration::from_secs(2));
+
+        let values = cache.values[0].unwrap();
+        assert_eq!(values.memory_current_bytes, Some(4096));
+        assert_eq!(values.memory_max_bytes, None);
+        // 2 seconds of cpu time in 2 seconds on 2 cores
+        assert_eq!(values.cpu_usage_percent, Some(50.0));
+        assert_eq!(values.throttled_periods_per_second, Some(2.0));
+        assert_eq!(values.io_read_bytes_per_second, Some(3000.0));
+        assert_eq!(values.io_write_bytes_per_second, Some(3000.0));
+    }
+
+    #[test]
+    fn test_refresh_of_missing_cgroup() {
+        let test_dir = TestDir::new("cgroup");
+        let cgroups = [CgroupStruct {
+            graph_name: "app".to_string(),
+            cgroup_path: "/app.service".to_string(),
+        }];
+        let mut cache = CgroupStatsCache::new(test_dir.path_str(), &cgroups, 2);
+        cache.refresh(Duration::from_secs(1));
+        assert!(cache.values[0].is_none());
+
+        // Cgroup without cpu.stat and io.stat, so rates cannot be computed
+        test_dir.write("app.service/memory.current", "100\n");
+        cache.refresh(Duration::from_secs(1));
+        let values = cache.values[0].unwrap();
+        assert_eq!(values.memory_current_bytes, Some(100));
+        assert_eq!(values.cpu_usage_percent, None);
+        assert_eq!(values.io_read_bytes_per_second, None);
+    }
+}
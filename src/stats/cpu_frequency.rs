@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Default, Debug, Clone)]
+pub struct CpuFrequencyStatsCache {
+    // None if frequency of core cannot be read e.g. core is offline or cpufreq is not supported
+    pub frequencies_mhz: Vec<Option<f64>>,
+    core_count: usize,
+    sysfs_path: PathBuf,
+}
+
+impl CpuFrequencyStatsCache {
+    pub fn new(sysfs_path: &str, core_count: usize) -> Self {
+        let mut cpu_frequency_stats = CpuFrequencyStatsCache {
+            frequencies_mhz: Vec::new(),
+            core_count,
+            sysfs_path: PathBuf::from(sysfs_path),
+        };
+        cpu_frequency_stats.refresh();
+        cpu_frequency_stats
+    }
+
+    pub fn refresh(&mut self) {
+        self.frequencies_mhz = (0..self.core_count).map(|idx| read_core_frequency(&self.sysfs_path, idx)).collect();
+    }
+}
+
+// Kernel provides frequency in kHz
+fn read_core_frequency(sysfs_path: &Path, core_idx: usize) -> Option<f64> {
+    let path = sysfs_path.join(format!("devices/system/cpu/cpu{core_idx}/cpufreq/scaling_cur_freq"));
+    let content = fs::read_to_string(path).ok()?;
+    content.trim().parse::<u64>().ok().map(|khz| khz as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_frequencies_are_read_in_mhz() {
+        let test_dir = TestDir::new("cpu_frequency");
+        test_dir.write("devices/system/cpu/cpu0/cpufreq/scaling_cur_freq", "2400000\n");
+        test_dir.write("devices/system/cpu/cpu1/cpufreq/scaling_cur_freq", "800500\n");
+
+        let cache = CpuFrequencyStatsCache::new(test_dir.path_str(), 2);
+        assert_eq!(cache.frequencies_mhz, vec![Some(2400.0), Some(800.5)]);
+    }
+
+    #[test]
+    fn test_missing_unreadable_and_broken_frequencies_are_none() {
+        let test_dir = TestDir::new("cpu_frequency");
+        test_dir.write("devices/system/cpu/cpu0/cpufreq/scaling_cur_freq", "1000000\n");
+        test_dir.make_unreadable("devices/system/cpu/cpu1/cpufreq/scaling_cur_freq");
+        test_dir.write("devices/system/cpu/cpu2/cpufreq/scaling_cur_freq", "<unknown>\n");
+
+        let cache = CpuFrequencyStatsCache::new(test_dir.path_str(), 4);
+        assert_eq!(cache.frequencies_mhz, vec![Some(1000.0), None, None, None]);
+    }
+
+    #[test]
+    fn test_refresh_reads_current_frequency() {
+        let test_dir = TestDir::new("cpu_frequency");
+        test_dir.write("devices/system/cpu/cpu0/cpufreq/scaling_cur_freq", "1000000\n");
+        let mut cache = CpuFrequencyStatsCache::new(test_dir.path_str(), 1);
+
+        test_dir.write("devices/system/cpu/cpu0/cpufreq/scaling_cur_freq", "3000000\n");
+        cache.refresh();
+        assert_eq!(cache.frequencies_mhz, vec![Some(3000.0)]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use nix::unistd::{sysconf, SysconfVar};
+
+// Sockets memory is reported by kernel in pages
+#[derive(Default, Debug, Clone)]
+pub struct DescriptorsStatsCache {
+    pub open_files: Option<u64>,
+    pub open_files_limit: Option<u64>,
+    pub sockets_used: Option<u64>,
+    pub tcp_in_use: Option<u64>,
+    pub tcp_orphan: Option<u64>,
+    pub tcp_time_wait: Option<u64>,
+    pub tcp_memory_bytes: Option<u64>,
+    pub udp_in_use: Option<u64>,
+    pub udp_memory_bytes: Option<u64>,
+    page_size: u64,
+}
+
+impl DescriptorsStatsCache {
+    pub fn new() -> Self {
+        let mut descriptors_stats = DescriptorsStatsCache {
+            page_size: sysconf(SysconfVar::PAGE_SIZE).ok().flatten().map_or(4096, |e| e as u64),
+            ..Default::default()
+        };
+        descriptors_stats.refresh();
+        descriptors_stats
+    }
+
+    pub fn refresh(&mut self) {
+        self.read_from_proc(Path::new("/proc"));
+    }
+
+    fn read_from_proc(&mut self, proc_path: &Path) {
+        // Line looks like - 279	0	613820 (allocated, allocated but unused, maximum)
+        let file_nr = fs::read_to_string(proc_path.join("sys/fs/file-nr"))
+            .map(|content| content.split_whitespace().filter_map(|e| e.parse::<u64>().ok()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        self.open_files = match (file_nr.first(), file_nr.get(1)) {
+            (Some(allocated), Some(unused)) => Some(allocated.saturating_sub(*unused)),
+            _ => None,
+        };
+        self.open_files_limit = file_nr.get(2).copied();
+
+        let sockstat = read_sockstat(&proc_path.join("net/sockstat"));
+        let get = |protocol: &str, key: &str| sockstat.get(protocol).and_then(|values| values.get(key)).copied();
+        self.sockets_used = get("sockets", "used");
+        self.tcp_in_use = get("TCP", "inuse");
+        self.tcp_orphan = get("TCP", "orphan");
+        self.tcp_time_wait = get("TCP", "tw");
+        self.tcp_memory_bytes = get("TCP", "mem").map(|pages| pages * self.page_size);
+        self.udp_in_use = get("UDP", "inuse");
+        self.udp_memory_bytes = get("UDP", "mem").map(|pages| pages * self.page_size);
+    }
+}
+
+// Line looks like - TCP: inuse 4 orphan 0 tw 0 alloc 4 mem 0
+fn read_sockstat(sockstat_path: &Path) -> HashMap<String, HashMap<String, u64>> {
+    let Ok(content) = fs::read_to_string(sockstat_path) else {
+        return HashMap::new();
+    };
+
+    let mut sockstat = HashMap::new();
+    for line in content.lines() {
+        let Some((protocol, values)) = line.split_once(':') else {
+            continue;
+        };
+        let split = values.split_whitespace().collect::<Vec<_>>();
+        let values = split
+            .chunks_exact(2)
+            .filter_map(|pair| pair[1].parse::<u64>().ok().map(|value| (pair[0].to_string(), value)))
+            .collect::<HashMap<_, _>>();
+        sockstat.insert(protocol.to_string(), values);
+    }
+    sockstat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    fn read_descriptors(test_dir: &TestDir) -> DescriptorsStatsCache {
+        let mut descriptors_stats = DescriptorsStatsCache {
+            page_size: 4096,
+            ..Default::default()
+        };
+        descriptors_stats.read_from_proc(&test_dir.path);
+        descriptors_stats
+    }
+
+    #[test]
+    fn test_file_nr_and_sockstat() {
+        let test_dir = TestDir::new("descriptors");
+        test_dir.write("sys/fs/file-nr", "2790\t90\t613820\n");
+        test_dir.write(
+            "net/sockstat",
+            "sockets: used 312\nTCP: inuse 14 orphan 1 tw 7 alloc 20 mem 3\nUDP: inuse 5 mem 2\nUDPLITE: inuse 0\nRAW: inuse 0\nFRAG: inuse 0 memory 0\n",
+        );
+
+        let descriptors_stats = read_descriptors(&test_dir);
+        assert_eq!(descriptors_stats.open_files, Some(2700));
+        assert_eq!(descriptors_stats.open_files_limit, Some(613820));
+        assert_eq!(descriptors_stats.sockets_used, Some(312));
+        assert_eq!(descriptors_stats.tcp_in_use, Some(14));
+        assert_eq!(descriptors_stats.tcp_orphan, Some(1));
+        assert_eq!(descriptors_stats.tcp_time_wait, Some(7));
+        assert_eq!(descriptors_stats.tcp_memory_bytes, Some(3 * 4096));
+        assert_eq!(descriptors_stats.udp_in_use, Some(5));
+        assert_eq!(descriptors_stats.udp_memory_bytes, Some(2 * 4096));
+    }
+
+    #[test]
+    fn test_malformed_files() {
+        let test_dir = TestDir::new("descriptors");
+        test_dir.write("sys/fs/file-nr", "2790\n");
+        test_dir.write("net/sockstat", "sockets used 312\nTCP: inuse abc orphan 1 tw\nUDP:\n");
+
+        let descriptors_stats = read_descriptors(&test_dir);
+        assert_eq!(descriptors_stats.open_files, None);
+        assert_eq!(descriptors_stats.open_files_limit, None);
+        assert_eq!(descriptors_stats.sockets_used, None);
+        assert_eq!(descriptors_stats.tcp_in_use, None);
+        assert_eq!(descriptors_stats.tcp_orphan, Some(1));
+        assert_eq!(descriptors_stats.tcp_time_wait, None);
+        assert_eq!(descriptors_stats.udp_in_use, None);
+    }
+
+    #[test]
+    fn test_missing_files() {
+        let test_dir = TestDir::new("descriptors");
+        let descriptors_stats = read_descriptors(&test_dir);
+        assert_eq!(descriptors_stats.open_files, None);
+        assert_eq!(descriptors_stats.open_files_limit, None);
+        assert_eq!(descriptors_stats.sockets_used, None);
+        assert_eq!(descriptors_stats.tcp_memory_bytes, None);
+        assert_eq!(descriptors_stats.udp_memory_bytes, None);
+    }
+}
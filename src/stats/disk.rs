@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Error};
+
+// Linux always counts sectors in /proc/diskstats in 512 bytes units, regardless of real device sector size
+const SECTOR_SIZE: u64 = 512;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DiskSample {
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+    pub io_time_ms: u64,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DiskRates {
+    pub read_bytes_per_second: f64,
+    pub write_bytes_per_second: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub busy_percent: f64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct DiskStatsCache {
+    // Devices found when app started, only they are tracked
+    pub devices: Vec<String>,
+    // None if device disappeared or no previous sample is available
+    pub rates: Vec<Option<DiskRates>>,
+    previous_samples: HashMap<String, DiskSample>,
+    sysfs_path: PathBuf,
+}
+
+impl DiskStatsCache {
+    pub fn new(sysfs_path: &str) -> Result<Self, Error> {
+        let sysfs_path = PathBuf::from(sysfs_path);
+        let previous_samples = read_disk_stats(Path::new("/proc/diskstats"), &sysfs_path)?;
+        let mut devices = previous_samples.keys().cloned().collect::<Vec<_>>();
+        devices.sort();
+
+        Ok(DiskStatsCache {
+            rates: vec![None; devices.len()],
+            devices,
+            previous_samples,
+            sysfs_path,
+        })
+    }
+
+    pub fn refresh(&mut self, elapsed: Duration) -> Result<(), Error> {
+        let current_samples = read_disk_stats(Path::new("/proc/diskstats"), &self.sysfs_path)?;
+        let seconds = elapsed.as_secs_f64();
+
+        self.rates = self
+            .devices
+            .iter()
+            .map(|device| {
+                let (Some(previous), Some(current)) = (self.previous_samples.get(device), current_samples.get(device)) else {
+                    return None;
+                };
+                if seconds <= 0.0 {
+                    return None;
+                }
+                Some(DiskRates {
+                    read_bytes_per_second: (current.sectors_read.saturating_sub(previous.sectors_read) * SECTOR_SIZE) as f64 / seconds,
+                    write_bytes_per_second: (current.sectors_written.saturating_sub(previous.sectors_written) * SECTOR_SIZE) as f64 / seconds,
+                    read_iops: current.reads_completed.saturating_sub(previous.reads_completed) as f64 / seconds,
+                    write_iops: current.writes_completed.saturating_sub(previous.writes_completed) as f64 / seconds,
+                    busy_percent: (current.io_time_ms.saturating_sub(previous.io_time_ms) as f64 / 10.0 / seconds).min(100.0),
+                })
+            })
+            .collect();
+        self.previous_samples = current_samples;
+
+        Ok(())
+    }
+}
+
+// Only whole devices listed in <sysfs>/block are used, because partitions would count the same IO twice
+// Loop and ram devices are virtual and only add noise to results
+fn read_disk_stats(diskstats_path: &Path, sysfs_path: &Path) -> Result<HashMap<String, DiskSample>, Error> {
+    let content = fs::read_to_string(diskstats_path).with_context(|| format!("Failed to read {}", diskstats_path.display()))?;
+
+    let mut samples = HashMap::new();
+    for line in content.lines() {
+        let split = line.split_whitespace().collect::<Vec<_>>();
+        if split.len() < 13 {
+            continue;
+        }
+        let name = split[2];
+        if name.starts_with("loop") || name.starts_with("ram") || !is_whole_device(sysfs_path, name) {
+            continue;
+        }
+        let parse = |idx: usize| split[idx].parse::<u64>().unwrap_or(0);
+        samples.insert(
+            name.to_string(),
+            DiskSample {
+                reads_completed: parse(3),
+                sectors_read: parse(5),
+                writes_completed: parse(7),
+                sectors_written: parse(9),
+                io_time_ms: parse(12),
+            },
+        );
+    }
+
+    Ok(samples)
+}
+
+// Sysfs uses "!" instead of "/" in names of devices like cciss/c0d0
+fn is_whole_device(sysfs_path: &Path, name: &str) -> bool {
+    let device_path = sysfs_path.join("block").join(name.replace('/', "!"));
+    device_path.exists() && !device_path.join("partition").exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    const DISKSTATS: &str = "   7       0 loop0 52 0 2088 10 0 0 0 0 0 20 10 0 0 0 0 0 0
+   1       0 ram0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+   8       0 sda 1000 10 20000 500 300 5 6000 200 0 700 700 0 0 0 0 0 0
+   8       1 sda1 900 10 18000 450 300 5 6000 200 0 650 650 0 0 0 0 0 0
+ 259       0 nvme0n1 200 0 4000 40 100 0 800 20 0 60 60 0 0 0 0 0 0
+ 259       1 nvme0n1p1 200 0 4000 40 100 0 800 20 0 60 60 0 0 0 0 0 0
+ 104       0 cciss/c0d0 5 0 40 1 0 0 0 0 0 1 1 0 0 0 0 0 0
+";
+
+    fn create_sysfs(test_dir: &TestDir) {
+        for device in ["loop0", "ram0", "sda", "nvme0n1", "cciss!c0d0"] {
+            test_dir.write(&format!("sys/block/{device}/size"), "0\n");
+        }
+        // In real sysfs partitions are only in directory of their parent device, fake one is also listed to check partition file
+        test_dir.write("sys/block/sda/sda1/partition", "1\n");
+        test_dir.write("sys/block/nvme0n1p1/partition", "1\n");
+    }
+
+    #[test]
+    fn test_only_whole_devices_are_read() {
+        let test_dir = TestDir::new("disk");
+        create_sysfs(&test_dir);
+        test_dir.write("diskstats", DISKSTATS);
+
+        let samples = read_disk_stats(&test_dir.path.join("diskstats"), &test_dir.path.join("sys")).unwrap();
+        let mut devices = samples.keys().cloned().collect::<Vec<_>>();
+        devices.sort();
+        assert_eq!(devices, vec!["cciss/c0d0", "nvme0n1", "sda"]);
+
+        let sda = samples["sda"];
+        assert_eq!(sda.reads_completed, 1000);
+        assert_eq!(sda.sectors_read, 20000);
+        assert_eq!(sda.writes_completed, 300);
+        assert_eq!(sda.sectors_written, 6000);
+        assert_eq!(sda.io_time_ms, 700);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped_or_zeroed() {
+        let test_dir = TestDir::new("disk");
+        create_sysfs(&test_dir);
+        test_dir.write(
+            "diskstats",
+            "   8       0 sda 1000 10 abc\n\n 259 0 nvme0n1 200 0 x 40 100 0 800 20 0 60 60\n",
+        );
+
+        let samples = read_disk_stats(&test_dir.path.join("diskstats"), &test_dir.path.join("sys")).unwrap();
+        assert!(!samples.contains_key("sda"));
+        let nvme = samples["nvme0n1"];
+        assert_eq!(nvme.reads_completed, 200);
+        assert_eq!(nvme.sectors_read, 0);
+        assert_eq!(nvme.io_time_ms, 60);
+    }
+
+    #[test]
+    fn test_missing_diskstats_and_sysfs() {
+        let test_dir = TestDir::new("disk");
+        assert!(read_disk_stats(&test_dir.path.join("diskstats"), &test_dir.path.join("sys")).is_err());
+
+        test_dir.write("diskstats", DISKSTATS);
+        let samples = read_disk_stats(&test_dir.path.join("diskstats"), &test_dir.path.join("sys")).unwrap();
+        assert!(samples.is_empty());
+    }
+}
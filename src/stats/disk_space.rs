@@ -0,0 +1,48 @@
+use nix::sys::statvfs::statvfs;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DiskSpace {
+    pub used_bytes: u64,
+    pub available_bytes: u64,
+    pub inodes_used_percent: f64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct DiskSpaceStatsCache {
+    pub mount_points: Vec<String>,
+    // None if mount point is not available e.g. device was unmounted
+    pub disk_spaces: Vec<Option<DiskSpace>>,
+}
+
+impl DiskSpaceStatsCache {
+    pub fn new(mount_points: &[String]) -> Self {
+        let mut disk_space_stats = DiskSpaceStatsCache {
+            mount_points: mount_points.to_vec(),
+            disk_spaces: Vec::new(),
+        };
+        disk_space_stats.refresh();
+        disk_space_stats
+    }
+
+    pub fn refresh(&mut self) {
+        self.disk_spaces = self.mount_points.iter().map(|mount_point| read_disk_space(mount_point)).collect();
+    }
+}
+
+// Available space is space available for unprivileged users, so used + available may be smaller than total size
+// Some filesystems like btrfs don't use inodes and report 0 as their count
+fn read_disk_space(mount_point: &str) -> Option<DiskSpace> {
+    let stat = statvfs(mount_point).ok()?;
+    let fragment_size = stat.fragment_size() as u64;
+    let files = stat.files() as u64;
+
+    Some(DiskSpace {
+        used_bytes: (stat.blocks() as u64).saturating_sub(stat.blocks_free() as u64) * fragment_size,
+        available_bytes: stat.blocks_available() as u64 * fragment_size,
+        inodes_used_percent: if files == 0 {
+            0.0
+        } else {
+            files.saturating_sub(stat.files_free() as u64) as f64 / files as f64 * 100.0
+        },
+    })
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Error};
+
+#[derive(Default, Debug, Clone)]
+pub struct MemInfoStatsCache {
+    // Values in bytes, keys are the same as in /proc/meminfo e.g. Cached, SReclaimable
+    values: HashMap<String, u64>,
+}
+
+impl MemInfoStatsCache {
+    pub fn new() -> Result<Self, Error> {
+        Ok(MemInfoStatsCache {
+            values: read_meminfo(Path::new("/proc/meminfo"))?,
+        })
+    }
+
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        self.values = read_meminfo(Path::new("/proc/meminfo"))?;
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.values.get(key).copied()
+    }
+}
+
+// Line looks like - Cached:          2167348 kB
+fn read_meminfo(meminfo_path: &Path) -> Result<HashMap<String, u64>, Error> {
+    let content = fs::read_to_string(meminfo_path).with_context(|| format!("Failed to read {}", meminfo_path.display()))?;
+
+    let mut values = HashMap::new();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut split = value.split_whitespace();
+        let Some(Ok(value)) = split.next().map(str::parse::<u64>) else {
+            continue;
+        };
+        let multiplier = if split.next() == Some("kB") { 1024 } else { 1 };
+        values.insert(key.to_string(), value * multiplier);
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_values_in_kb_and_without_unit() {
+        let test_dir = TestDir::new("meminfo");
+        test_dir.write(
+            "meminfo",
+            "MemTotal:        8048576 kB\nCached:          2167348 kB\nSReclaimable:     123456 kB\nHugePages_Total:       4\n",
+        );
+
+        let values = read_meminfo(&test_dir.path.join("meminfo")).unwrap();
+        assert_eq!(values["MemTotal"], 8048576 * 1024);
+        assert_eq!(values["Cached"], 2167348 * 1024);
+        assert_eq!(values["SReclaimable"], 123456 * 1024);
+        assert_eq!(values["HugePages_Total"], 4);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped() {
+        let test_dir = TestDir::new("meminfo");
+        test_dir.write("meminfo", "Cached 2167348 kB\nDirty:\nShmem:        abc kB\nBuffers:          1000 kB\n");
+
+        let values = read_meminfo(&test_dir.path.join("meminfo")).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["Buffers"], 1000 * 1024);
+    }
+
+    #[test]
+    fn test_missing_meminfo() {
+        let test_dir = TestDir::new("meminfo");
+        assert!(read_meminfo(&test_dir.path.join("meminfo")).is_err());
+    }
+}
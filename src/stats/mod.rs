@@ -0,0 +1,332 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Error};
+use sysinfo::System;
+
+use crate::enums::SimpleDataCollectionMode;
+use crate::model::Settings;
+use crate::stats::cgroup::CgroupStatsCache;
+use crate::stats::cpu_frequency::CpuFrequencyStatsCache;
+use crate::stats::descriptors::DescriptorsStatsCache;
+use crate::stats::disk::DiskStatsCache;
+use crate::stats::disk_space::DiskSpaceStatsCache;
+use crate::stats::meminfo::MemInfoStatsCache;
+use crate::stats::network::NetworkStatsCache;
+use crate::stats::power_supply::PowerSupplyStatsCache;
+use crate::stats::pressure::PressureStatsCache;
+use crate::stats::proc_stat::ProcStatStatsCache;
+use crate::stats::process_events::ProcessEventsStatsCache;
+use crate::stats::processes::ProcessesStatsCache;
+use crate::stats::tasks::TasksStatsCache;
+use crate::stats::tcp_states::TcpStatesStatsCache;
+use crate::stats::temperature::TemperatureStatsCache;
+use crate::stats::top_processes::TopProcessesStatsCache;
+use crate::stats::users::UsersStatsCache;
+use crate::stats::vmstat::VmStatStatsCache;
+
+pub mod cgroup;
+pub mod cpu_frequency;
+pub mod descriptors;
+pub mod disk;
+pub mod disk_space;
+pub mod meminfo;
+pub mod network;
+pub mod power_supply;
+pub mod pressure;
+pub mod proc_stat;
+pub mod process_details;
+pub mod process_events;
+pub mod processes;
+pub mod tasks;
+pub mod tcp_states;
+pub mod temperature;
+pub mod top_processes;
+pub mod users;
+pub mod vmstat;
+
+// Keeps data read directly from /proc and /sys files, together with previous samples of data which are collected as difference between ticks
+#[derive(Debug, Clone)]
+pub struct SystemStatsCache {
+    pub disk: Option<DiskStatsCache>,
+    pub network: Option<NetworkStatsCache>,
+    pub pressure: Option<PressureStatsCache>,
+    pub cpu_frequency: Option<CpuFrequencyStatsCache>,
+    pub temperature: Option<TemperatureStatsCache>,
+    pub power_supply: Option<PowerSupplyStatsCache>,
+    pub disk_space: Option<DiskSpaceStatsCache>,
+    pub proc_stat: Option<ProcStatStatsCache>,
+    pub meminfo: Option<MemInfoStatsCache>,
+    pub vmstat: Option<VmStatStatsCache>,
+    pub tasks: Option<TasksStatsCache>,
+    pub descriptors: Option<DescriptorsStatsCache>,
+    pub tcp_states: Option<TcpStatesStatsCache>,
+    pub cgroup: Option<CgroupStatsCache>,
+    pub users: Option<UsersStatsCache>,
+    pub top_processes: Option<TopProcessesStatsCache>,
+    pub process_events: Option<ProcessEventsStatsCache>,
+    // Shared by all stats which need data of many processes, available only when any of them is used
+    pub processes: Option<ProcessesStatsCache>,
+    last_refresh: Instant,
+}
+
+impl SystemStatsCache {
+    pub fn new(sys: &System, settings: &Settings) -> Result<Self, Error> {
+        let disk = if settings.collection_mode.iter().any(|e| e.is_disk()) {
+            Some(DiskStatsCache::new(&settings.sysfs_path)?)
+        } else {
+            None
+        };
+        let network = if settings.collection_mode.iter().any(|e| e.is_network()) {
+            Some(NetworkStatsCache::new(&settings.sysfs_path, settings.skip_virtual_network_interfaces)?)
+        } else {
+            None
+        };
+        let pressure = if settings.collection_mode.iter().any(|e| e.is_pressure()) {
+            Some(PressureStatsCache::new())
+        } else {
+            None
+        };
+        let cpu_frequency = if settings.collection_mode.contains(&SimpleDataCollectionMode::CPU_FREQUENCY_PER_CORE) {
+            Some(CpuFrequencyStatsCache::new(&settings.sysfs_path, sys.cpus().len()))
+        } else {
+            None
+        };
+        let temperature = if settings.collection_mode.contains(&SimpleDataCollectionMode::TEMPERATURE) {
+            Some(TemperatureStatsCache::new(&settings.sysfs_path))
+        } else {
+            None
+        };
+        let power_supply = if settings.collection_mode.iter().any(|e| e.is_power()) {
+            Some(PowerSupplyStatsCache::new(&settings.sysfs_path))
+        } else {
+            None
+        };
+        let disk_space = if settings.mount_points_to_check.is_empty() {
+            None
+        } else {
+            Some(DiskSpaceStatsCache::new(&settings.mount_points_to_check))
+        };
+        let proc_stat = if settings.collection_mode.iter().any(|e| e.is_cpu_state() || e.is_kernel_activity()) {
+            Some(ProcStatStatsCache::new(sys.cpus().len())?)
+        } else {
+            None
+        };
+        let meminfo = if settings.collection_mode.iter().any(|e| e.is_meminfo()) {
+            Some(MemInfoStatsCache::new()?)
+        } else {
+            None
+        };
+        let vmstat = if settings.collection_mode.iter().any(|e| e.is_vmstat()) {
+            Some(VmStatStatsCache::new()?)
+        } else {
+            None
+        };
+        let descriptors = if settings.collection_mode.iter().any(|e| e.is_descriptors()) {
+            Some(DescriptorsStatsCache::new())
+        } else {
+            None
+        };
+        let tcp_states = if settings.collection_mode.iter().any(|e| e.is_tcp_states()) {
+            Some(TcpStatesStatsCache::new(&settings.tcp_local_ports))
+        } else {
+            None
+        };
+        let cgroup = if settings.cgroups_to_check.is_empty() {
+            None
+        } else {
+            Some(CgroupStatsCache::new(&settings.cgroup_path, &settings.cgroups_to_check, sys.cpus().len()))
+        };
+
+        // Plain tracked processes read only their own pids, so they don't need all processes
+        let needs_owners = settings.collection_mode.iter().any(|e| e.is_users()) || settings.process_events;
+        let needs_processes = needs_owners
+            || settings.collection_mode.iter().any(|e| e.is_tasks())
+            || settings.top_processes > 0
+            || settings.process_cmd_to_search.iter().any(|e| e.include_children)
+            // Command is tracked together with its children, but it is started after this cache is created
+            || !settings.command_to_run.is_empty();
+        let processes = if needs_processes {
+            Some(ProcessesStatsCache::new(needs_owners)?)
+        } else {
+            None
+        };
+
+        let tasks = match &processes {
+            Some(processes) if settings.collection_mode.iter().any(|e| e.is_tasks()) => Some(TasksStatsCache::new(processes)),
+            _ => None,
+        };
+        let users = if settings.collection_mode.iter().any(|e| e.is_users()) {
+            Some(UsersStatsCache::new(&settings.users_to_check, settings.top_users, sys.cpus().len())?)
+        } else {
+            None
+        };
+        let top_processes = if settings.top_processes > 0 {
+            Some(TopProcessesStatsCache::new(
+                settings.top_processes,
+                settings.top_processes_interval,
+                sys.cpus().len(),
+            ))
+        } else {
+            None
+        };
+        let process_events = match &processes {
+            Some(processes) if settings.process_events => Some(ProcessEventsStatsCache::new(&settings.process_events_filters, processes)),
+            _ => None,
+        };
+
+        Ok(SystemStatsCache {
+            disk,
+            network,
+            pressure,
+            cpu_frequency,
+            temperature,
+            power_supply,
+            disk_space,
+            proc_stat,
+            meminfo,
+            vmstat,
+            tasks,
+            descriptors,
+            tcp_states,
+            cgroup,
+            users,
+            top_processes,
+            process_events,
+            processes,
+            last_refresh: Instant::now(),
+        })
+    }
+
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        let elapsed = self.last_refresh.elapsed();
+        self.last_refresh = Instant::now();
+
+        if let Some(disk) = &mut self.disk {
+            disk.refresh(elapsed)?;
+        }
+        if let Some(network) = &mut self.network {
+            network.refresh(elapsed)?;
+        }
+        if let Some(pressure) = &mut self.pressure {
+            pressure.refresh();
+        }
+        if let Some(cpu_frequency) = &mut self.cpu_frequency {
+            cpu_frequency.refresh();
+        }
+        if let Some(temperature) = &mut self.temperature {
+            temperature.refresh();
+        }
+        if let Some(power_supply) = &mut self.power_supply {
+            power_supply.refresh();
+        }
+        if let Some(disk_space) = &mut self.disk_space {
+            disk_space.refresh();
+        }
+        if let Some(proc_stat) = &mut self.proc_stat {
+            proc_stat.refresh(elapsed)?;
+        }
+        if let Some(meminfo) = &mut self.meminfo {
+            meminfo.refresh()?;
+        }
+        if let Some(vmstat) = &mut self.vmstat {
+            vmstat.refresh(elapsed)?;
+        }
+        if let Some(descriptors) = &mut self.descriptors {
+            descriptors.refresh();
+        }
+        if let Some(tcp_states) = &mut self.tcp_states {
+            tcp_states.refresh();
+        }
+        if let Some(cgroup) = &mut self.cgroup {
+            cgroup.refresh(elapsed);
+        }
+        if let Some(processes) = &mut self.processes {
+            processes.refresh()?;
+            if let Some(tasks) = &mut self.tasks {
+                tasks.refresh(processes);
+            }
+            if let Some(users) = &mut self.users {
+                users.refresh(processes);
+            }
+            if let Some(top_processes) = &mut self.top_processes {
+                top_processes.refresh(processes);
+            }
+            if let Some(process_events) = &mut self.process_events {
+                process_events.refresh(processes);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Names are saved in header line, so they cannot contain characters used as separators
+pub fn sanitize_item_name(name: &str) -> String {
+    name.replace([',', '=', ';', '|'], "_")
+}
+
+pub fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|e| e.trim().to_string())
+}
+
+// Sorted to always have the same order of sensors and other items
+pub fn read_sorted_dir(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut paths = entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+// Line looks like - 350735.47 234388.90
+pub fn read_uptime_seconds() -> Result<f64, Error> {
+    let content = fs::read_to_string("/proc/uptime").context("Failed to read /proc/uptime")?;
+    content
+        .split_whitespace()
+        .next()
+        .and_then(|e| e.parse::<f64>().ok())
+        .context("Failed to parse /proc/uptime")
+}
+
+// Fake sysfs/cgroupfs tree for tests, removed when dropped
+#[cfg(test)]
+pub struct TestDir {
+    pub path: PathBuf,
+}
+
+#[cfg(test)]
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        static COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let idx = COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let path = std::env::temp_dir().join(format!("system_info_collector_{name}_{}_{idx}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TestDir { path }
+    }
+
+    pub fn write(&self, relative_path: &str, content: &str) {
+        let path = self.path.join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    // Root can read files without read permission, so directory in place of file is used to fail reading it
+    pub fn make_unreadable(&self, relative_path: &str) {
+        fs::create_dir_all(self.path.join(relative_path)).unwrap();
+    }
+
+    pub fn path_str(&self) -> &str {
+        self.path.to_str().unwrap()
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Error};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct NetworkSample {
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub rx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+    pub tx_errors: u64,
+    pub tx_dropped: u64,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct NetworkRates {
+    pub rx_bytes_per_second: f64,
+    pub tx_bytes_per_second: f64,
+    pub rx_packets_per_second: f64,
+    pub tx_packets_per_second: f64,
+    pub rx_errors_per_second: f64,
+    pub tx_errors_per_second: f64,
+    pub rx_dropped_per_second: f64,
+    pub tx_dropped_per_second: f64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct NetworkStatsCache {
+    // Interfaces found when app started, only they are tracked
+    pub interfaces: Vec<String>,
+    // None if interface disappeared or no previous sample is available
+    pub rates: Vec<Option<NetworkRates>>,
+    previous_samples: HashMap<String, NetworkSample>,
+}
+
+impl NetworkStatsCache {
+    pub fn new(sysfs_path: &str, skip_virtual_interfaces: bool) -> Result<Self, Error> {
+        let previous_samples = read_network_stats(Path::new("/proc/net/dev"))?;
+        let mut interfaces = previous_samples
+            .keys()
+            .filter(|interface| !skip_virtual_interfaces || !is_virtual_interface(Path::new(sysfs_path), interface))
+            .cloned()
+            .collect::<Vec<_>>();
+        interfaces.sort();
+
+        Ok(NetworkStatsCache {
+            rates: vec![None; interfaces.len()],
+            interfaces,
+            previous_samples,
+        })
+    }
+
+    pub fn refresh(&mut self, elapsed: Duration) -> Result<(), Error> {
+        let current_samples = read_network_stats(Path::new("/proc/net/dev"))?;
+        let seconds = elapsed.as_secs_f64();
+
+        self.rates = self
+            .interfaces
+            .iter()
+            .map(|interface| {
+                let (Some(previous), Some(current)) = (self.previous_samples.get(interface), current_samples.get(interface)) else {
+                    return None;
+                };
+                if seconds <= 0.0 {
+                    return None;
+                }
+                Some(NetworkRates {
+                    rx_bytes_per_second: current.rx_bytes.saturating_sub(previous.rx_bytes) as f64 / seconds,
+                    tx_bytes_per_second: current.tx_bytes.saturating_sub(previous.tx_bytes) as f64 / seconds,
+                    rx_packets_per_second: current.rx_packets.saturating_sub(previous.rx_packets) as f64 / seconds,
+                    tx_packets_per_second: current.tx_packets.saturating_sub(previous.tx_packets) as f64 / seconds,
+                    rx_errors_per_second: current.rx_errors.saturating_sub(previous.rx_errors) as f64 / seconds,
+                    tx_errors_per_second: current.tx_errors.saturating_sub(previous.tx_errors) as f64 / seconds,
+                    rx_dropped_per_second: current.rx_dropped.saturating_sub(previous.rx_dropped) as f64 / seconds,
+                    tx_dropped_per_second: current.tx_dropped.saturating_sub(previous.tx_dropped) as f64 / seconds,
+                })
+            })
+            .collect();
+        self.previous_samples = current_samples;
+
+        Ok(())
+    }
+}
+
+// Loopback, bridges, veth, tun etc. are placed by kernel in virtual devices folder
+fn is_virtual_interface(sysfs_path: &Path, interface: &str) -> bool {
+    interface == "lo" || sysfs_path.join("devices/virtual/net").join(interface).exists()
+}
+
+// First two lines contains only header
+fn read_network_stats(net_dev_path: &Path) -> Result<HashMap<String, NetworkSample>, Error> {
+    let content = fs::read_to_string(net_dev_path).with_context(|| format!("Failed to read {}", net_dev_path.display()))?;
+
+    let mut samples = HashMap::new();
+    for line in content.lines().skip(2) {
+        let Some((name, values)) = line.split_once(':') else {
+            continue;
+        };
+        let split = values.split_whitespace().collect::<Vec<_>>();
+        if split.len() < 12 {
+            continue;
+        }
+        let parse = |idx: usize| split[idx].parse::<u64>().unwrap_or(0);
+        samples.insert(
+            name.trim().to_string(),
+            NetworkSample {
+                rx_bytes: parse(0),
+                rx_packets: parse(1),
+                rx_errors: parse(2),
+                rx_dropped: parse(3),
+                tx_bytes: parse(8),
+                tx_packets: parse(9),
+                tx_errors: parse(10),
+                tx_dropped: parse(11),
+            },
+        );
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    const NET_DEV: &str = "Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  123456     100    0    0    0     0          0         0   123456     100    0    0    0     0       0          0
+  eth0: 98765432  65000    3    7    0     0          0        12 12345678  43000    1    2    0     0       0          0
+docker0:    5000      50    0    0    0     0          0         0     6000      60    0    0    0     0       0          0
+";
+
+    #[test]
+    fn test_interfaces_are_parsed() {
+        let test_dir = TestDir::new("network");
+        test_dir.write("dev", NET_DEV);
+
+        let samples = read_network_stats(&test_dir.path.join("dev")).unwrap();
+        assert_eq!(samples.len(), 3);
+        let eth0 = samples["eth0"];
+        assert_eq!(eth0.rx_bytes, 98765432);
+        assert_eq!(eth0.rx_packets, 65000);
+        assert_eq!(eth0.rx_errors, 3);
+        assert_eq!(eth0.rx_dropped, 7);
+        assert_eq!(eth0.tx_bytes, 12345678);
+        assert_eq!(eth0.tx_packets, 43000);
+        assert_eq!(eth0.tx_errors, 1);
+        assert_eq!(eth0.tx_dropped, 2);
+        assert_eq!(samples["docker0"].rx_bytes, 5000);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped_or_zeroed() {
+        let test_dir = TestDir::new("network");
+        test_dir.write(
+            "dev",
+            "header\nheader\nno colon here 1 2 3\n  eth0: 1 2 3\n  eth1: x 20 0 0 0 0 0 0 300 30 0 0 0 0 0 0\n",
+        );
+
+        let samples = read_network_stats(&test_dir.path.join("dev")).unwrap();
+        assert_eq!(samples.len(), 1);
+        let eth1 = samples["eth1"];
+        assert_eq!(eth1.rx_bytes, 0);
+        assert_eq!(eth1.rx_packets, 20);
+        assert_eq!(eth1.tx_bytes, 300);
+    }
+
+    #[test]
+    fn test_missing_net_dev() {
+        let test_dir = TestDir::new("network");
+        assert!(read_network_stats(&test_dir.path.join("dev")).is_err());
+    }
+
+    #[test]
+    fn test_virtual_interfaces() {
+        let test_dir = TestDir::new("network");
+        test_dir.write("devices/virtual/net/docker0/mtu", "1500\n");
+        test_dir.write("devices/pci0000:00/0000:00:03.0/net/eth0/mtu", "1500\n");
+
+        assert!(is_virtual_interface(&test_dir.path, "lo"));
+        assert!(is_virtual_interface(&test_dir.path, "docker0"));
+        assert!(!is_virtual_interface(&test_dir.path, "eth0"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+
+use crate::stats::{read_sorted_dir, read_trimmed};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PowerSupplyValues {
+    pub capacity_percent: Option<f64>,
+    pub status: Option<f64>,
+    pub voltage: Option<f64>,
+    pub current: Option<f64>,
+    pub power: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct PowerSupplyStatsCache {
+    // Supplies found when app started, only they are tracked
+    pub supplies: Vec<PathBuf>,
+    pub values: Vec<PowerSupplyValues>,
+}
+
+impl PowerSupplyStatsCache {
+    pub fn new(sysfs_path: &str) -> Self {
+        // AC adapters provide only online status, so they are skipped
+        let supplies = read_sorted_dir(&Path::new(sysfs_path).join("class/power_supply"))
+            .into_iter()
+            .filter(|path| {
+                ["capacity", "status", "voltage_now", "current_now", "power_now"]
+                    .iter()
+                    .any(|file| path.join(file).exists())
+            })
+            .collect();
+
+        let mut power_supply_stats = PowerSupplyStatsCache {
+            supplies,
+            values: Vec::new(),
+        };
+        power_supply_stats.refresh();
+        power_supply_stats
+    }
+
+    pub fn refresh(&mut self) {
+        self.values = self.supplies.iter().map(|path| read_power_supply(path)).collect();
+    }
+
+    pub fn get_supply_names(&self) -> Vec<String> {
+        self.supplies
+            .iter()
+            .map(|path| path.file_name().unwrap_or_default().to_string_lossy().to_string())
+            .collect()
+    }
+}
+
+// Status is saved as number, to be able to show it on plot
+fn status_to_number(status: &str) -> Option<f64> {
+    match status {
+        "Discharging" => Some(0.0),
+        "Not charging" => Some(1.0),
+        "Charging" => Some(2.0),
+        "Full" => Some(3.0),
+        _ => None,
+    }
+}
+
+// Kernel provides values in microvolts, microamperes and microwatts
+// Some batteries don't provide power_now, then it is calculated from voltage and current
+fn read_power_supply(path: &Path) -> PowerSupplyValues {
+    let read_value = |file: &str| read_trimmed(&path.join(file)).and_then(|e| e.parse::<f64>().ok());
+    let voltage = read_value("voltage_now").map(|e| e / 1_000_000.0);
+    let current = read_value("current_now").map(|e| e / 1_000_000.0);
+    let power = match (read_value("power_now"), voltage, current) {
+        (Some(power), _, _) => Some(power / 1_000_000.0),
+        (None, Some(voltage), Some(current)) => Some(voltage * current),
+        _ => None,
+    };
+
+    PowerSupplyValues {
+        capacity_percent: read_value("capacity"),
+        status: read_trimmed(&path.join("status")).and_then(|e| status_to_number(&e)),
+        voltage,
+        current,
+        power,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_values_are_converted_from_micro_units() {
+        let test_dir = TestDir::new("power_supply");
+        test_dir.write("class/power_supply/BAT0/capacity", "87\n");
+        test_dir.write("class/power_supply/BAT0/status", "Discharging\n");
+        test_dir.write("class/power_supply/BAT0/voltage_now", "12000000\n");
+        test_dir.write("class/power_supply/BAT0/current_now", "1500000\n");
+        test_dir.write("class/power_supply/BAT0/power_now", "20000000\n");
+
+        let cache = PowerSupplyStatsCache::new(test_dir.path_str());
+        assert_eq!(cache.get_supply_names(), vec!["BAT0".to_string()]);
+        let values = cache.values[0];
+        assert_eq!(values.capacity_percent, Some(87.0));
+        assert_eq!(values.status, Some(0.0));
+        assert_eq!(values.voltage, Some(12.0));
+        assert_eq!(values.current, Some(1.5));
+        assert_eq!(values.power, Some(20.0));
+    }
+
+    #[test]
+    fn test_power_is_calculated_when_power_now_is_missing() {
+        let test_dir = TestDir::new("power_supply");
+        test_dir.write("class/power_supply/BAT0/voltage_now", "10000000\n");
+        test_dir.write("class/power_supply/BAT0/current_now", "2000000\n");
+
+        let cache = PowerSupplyStatsCache::new(test_dir.path_str());
+        assert_eq!(cache.values[0].power, Some(20.0));
+    }
+
+    #[test]
+    fn test_missing_unreadable_and_unknown_values_are_none() {
+        let test_dir = TestDir::new("power_supply");
+        test_dir.write("class/power_supply/BAT0/status", "Unknown\n");
+        test_dir.write("class/power_supply/BAT0/capacity", "not a number\n");
+        test_dir.make_unreadable("class/power_supply/BAT0/voltage_now");
+        test_dir.write("class/power_supply/BAT0/current_now", "1000000\n");
+
+        let cache = PowerSupplyStatsCache::new(test_dir.path_str());
+        let values = cache.values[0];
+        assert_eq!(values.capacity_percent, None);
+        assert_eq!(values.status, None);
+        assert_eq!(values.voltage, None);
+        assert_eq!(values.current, Some(1.0));
+        // Power cannot be calculated without voltage
+        assert_eq!(values.power, None);
+    }
+
+    #[test]
+    fn test_supplies_are_sorted_and_ac_adapters_skipped() {
+        let test_dir = TestDir::new("power_supply");
+        test_dir.write("class/power_supply/BAT1/status", "Charging\n");
+        test_dir.write("class/power_supply/AC/online", "1\n");
+        test_dir.write("class/power_supply/BAT0/status", "Full\n");
+
+        let cache = PowerSupplyStatsCache::new(test_dir.path_str());
+        assert_eq!(cache.get_supply_names(), vec!["BAT0".to_string(), "BAT1".to_string()]);
+        assert_eq!(cache.values[0].status, Some(3.0));
+        assert_eq!(cache.values[1].status, Some(2.0));
+    }
+
+    #[test]
+    fn test_without_power_supplies() {
+        let test_dir = TestDir::new("power_supply");
+        let cache = PowerSupplyStatsCache::new(test_dir.path_str());
+        assert!(cache.supplies.is_empty());
+        assert!(cache.values.is_empty());
+    }
+
+    #[test]
+    fn test_status_to_number() {
+        assert_eq!(status_to_number("Not charging"), Some(1.0));
+        assert_eq!(status_to_number("charging"), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::Path;
+
+// Kernels older than 5.13 don't provide full line for cpu, and kernels without PSI support don't have these files at all
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PressureValues {
+    pub some_avg10: Option<f64>,
+    pub full_avg10: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct PressureStatsCache {
+    pub cpu: PressureValues,
+    pub memory: PressureValues,
+    pub io: PressureValues,
+}
+
+impl PressureStatsCache {
+    pub fn new() -> Self {
+        let mut pressure_stats = PressureStatsCache::default();
+        pressure_stats.refresh();
+        pressure_stats
+    }
+
+    // Values are already averaged by kernel, so there is no need to compute difference between ticks
+    pub fn refresh(&mut self) {
+        let pressure_path = Path::new("/proc/pressure");
+        self.cpu = read_pressure(&pressure_path.join("cpu"));
+        self.memory = read_pressure(&pressure_path.join("memory"));
+        self.io = read_pressure(&pressure_path.join("io"));
+    }
+}
+
+// Line looks like - some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+fn read_pressure(path: &Path) -> PressureValues {
+    let mut values = PressureValues::default();
+    let Ok(content) = fs::read_to_string(path) else {
+        return values;
+    };
+
+    for line in content.lines() {
+        let mut split = line.split_whitespace();
+        let kind = split.next();
+        let avg10 = split.find_map(|e| e.strip_prefix("avg10=")).and_then(|e| e.parse::<f64>().ok());
+        match kind {
+            Some("some") => values.some_avg10 = avg10,
+            Some("full") => values.full_avg10 = avg10,
+            _ => {}
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_some_and_full_lines() {
+        let test_dir = TestDir::new("pressure");
+        test_dir.write(
+            "memory",
+            "some avg10=1.53 avg60=0.87 avg300=0.21 total=123456\nfull avg10=0.42 avg60=0.10 avg300=0.02 total=65432\n",
+        );
+
+        let values = read_pressure(&test_dir.path.join("memory"));
+        assert_eq!(values.some_avg10, Some(1.53));
+        assert_eq!(values.full_avg10, Some(0.42));
+    }
+
+    #[test]
+    fn test_cpu_without_full_line() {
+        let test_dir = TestDir::new("pressure");
+        test_dir.write("cpu", "some avg10=12.00 avg60=8.00 avg300=3.00 total=999\n");
+
+        let values = read_pressure(&test_dir.path.join("cpu"));
+        assert_eq!(values.some_avg10, Some(12.0));
+        assert_eq!(values.full_avg10, None);
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        let test_dir = TestDir::new("pressure");
+        test_dir.write(
+            "io",
+            "some avg10=abc avg60=0.00 avg300=0.00 total=0\nfull avg60=1.00 total=0\nother avg10=5.00\n\n",
+        );
+
+        let values = read_pressure(&test_dir.path.join("io"));
+        assert_eq!(values.some_avg10, None);
+        assert_eq!(values.full_avg10, None);
+    }
+
+    #[test]
+    fn test_missing_and_unreadable_files() {
+        let test_dir = TestDir::new("pressure");
+        test_dir.make_unreadable("io");
+        for file in ["io", "missing"] {
+            let values = read_pressure(&test_dir.path.join(file));
+            assert_eq!(values.some_avg10, None, "{file}");
+            assert_eq!(values.full_avg10, None, "{file}");
+        }
+    }
+}
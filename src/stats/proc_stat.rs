@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+
+use anyhow::{Context, Error};
+
+// Time spent in each state in USER_HZ units, guest times are already included in user and nice
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+}
+
+// Percentage of time spent in each state since previous check
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuStates {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+}
+
+impl CpuStates {
+    fn from_times_difference(previous: &CpuTimes, current: &CpuTimes) -> Option<Self> {
+        let total = current.total().saturating_sub(previous.total());
+        if total == 0 {
+            return None;
+        }
+        let percent = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / total as f64 * 100.0;
+        Some(CpuStates {
+            user: percent(current.user, previous.user),
+            nice: percent(current.nice, previous.nice),
+            system: percent(current.system, previous.system),
+            idle: percent(current.idle, previous.idle),
+            iowait: percent(current.iowait, previous.iowait),
+            irq: percent(current.irq, previous.irq),
+            softirq: percent(current.softirq, previous.softirq),
+            steal: percent(current.steal, previous.steal),
+        })
+    }
+}
+
+// Processes running and blocked are current values, not counters, so they are saved as is
+#[derive(Default, Debug, Clone, Copy)]
+pub struct KernelActivity {
+    pub context_switches_per_second: f64,
+    pub interrupts_per_second: f64,
+    pub forks_per_second: f64,
+    pub processes_running: f64,
+    pub processes_blocked: f64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ProcStatSample {
+    pub total_times: Option<CpuTimes>,
+    pub core_times: HashMap<usize, CpuTimes>,
+    pub context_switches: u64,
+    pub interrupts: u64,
+    pub forks: u64,
+    pub processes_running: u64,
+    pub processes_blocked: u64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ProcStatStatsCache {
+    // None if no previous sample is available or core is offline
+    pub total_states: Option<CpuStates>,
+    pub core_states: Vec<Option<CpuStates>>,
+    pub kernel_activity: Option<KernelActivity>,
+    core_count: usize,
+    previous_sample: ProcStatSample,
+}
+
+impl ProcStatStatsCache {
+    pub fn new(core_count: usize) -> Result<Self, Error> {
+        Ok(ProcStatStatsCache {
+            total_states: None,
+            core_states: vec![None; core_count],
+            kernel_activity: None,
+            core_count,
+            previous_sample: read_proc_stat()?,
+        })
+    }
+
+    pub fn refresh(&mut self, elapsed: Duration) -> Result<(), Error> {
+        let current_sample = read_proc_stat()?;
+        let seconds = elapsed.as_secs_f64();
+
+        self.total_states = match (&self.previous_sample.total_times, &current_sample.total_times) {
+            (Some(previous), Some(current)) => CpuStates::from_times_difference(previous, current),
+            _ => None,
+        };
+        self.core_states = (0..self.core_count)
+            .map(|idx| {
+                let (Some(previous), Some(current)) = (self.previous_sample.core_times.get(&idx), current_sample.core_times.get(&idx)) else {
+                    return None;
+                };
+                CpuStates::from_times_difference(previous, current)
+            })
+            .collect();
+        self.kernel_activity = if seconds > 0.0 {
+            let previous = &self.previous_sample;
+            Some(KernelActivity {
+                context_switches_per_second: current_sample.context_switches.saturating_sub(previous.context_switches) as f64 / seconds,
+                interrupts_per_second: current_sample.interrupts.saturating_sub(previous.interrupts) as f64 / seconds,
+                forks_per_second: current_sample.forks.saturating_sub(previous.forks) as f64 / seconds,
+                processes_running: current_sample.processes_running as f64,
+                processes_blocked: current_sample.processes_blocked as f64,
+            })
+        } else {
+            None
+        };
+        self.previous_sample = current_sample;
+
+        Ok(())
+    }
+}
+
+// Line looks like - cpu0 26361 0 3227 36464 503 0 1 127 0 0
+fn parse_cpu_times(values: &[&str]) -> CpuTimes {
+    let parse = |idx: usize| values.get(idx).and_then(|e| e.parse::<u64>().ok()).unwrap_or(0);
+    CpuTimes {
+        user: parse(0),
+        nice: parse(1),
+        system: parse(2),
+        idle: parse(3),
+        iowait: parse(4),
+        irq: parse(5),
+        softirq: parse(6),
+        steal: parse(7),
+    }
+}
+
+fn read_proc_stat() -> Result<ProcStatSample, Error> {
+    let content = fs::read_to_string("/proc/stat").context("Failed to read /proc/stat")?;
+    Ok(parse_proc_stat(&content))
+}
+
+fn parse_proc_stat(content: &str) -> ProcStatSample {
+    let mut sample = ProcStatSample::default();
+    for line in content.lines() {
+        let split = line.split_whitespace().collect::<Vec<_>>();
+        let Some((name, values)) = split.split_first() else {
+            continue;
+        };
+        // Only first value of intr line is needed, rest are counters of each interrupt
+        let first_value = || values.first().and_then(|e| e.parse::<u64>().ok()).unwrap_or(0);
+        match *name {
+            "ctxt" => sample.context_switches = first_value(),
+            "intr" => sample.interrupts = first_value(),
+            "processes" => sample.forks = first_value(),
+            "procs_running" => sample.processes_running = first_value(),
+            "procs_blocked" => sample.processes_blocked = first_value(),
+            _ => {}
+        }
+        if *name == "cpu" {
+            sample.total_times = Some(parse_cpu_times(values));
+        } else if let Some(core_idx) = name.strip_prefix("cpu").and_then(|e| e.parse::<usize>().ok()) {
+            sample.core_times.insert(core_idx, parse_cpu_times(values));
+        }
+    }
+
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROC_STAT: &str = "cpu  1000 10 300 5000 40 0 6 0 0 0
+cpu0 600 10 200 2000 30 0 5 0 0 0
+cpu1 400 0 100 3000 10 0 1 0 0 0
+intr 123456 20 0 0 0 0
+ctxt 987654
+btime 1700000000
+processes 4321
+procs_running 3
+procs_blocked 1
+softirq 5000 0 100 0 0 0 0 0 0 0 0
+";
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let sample = parse_proc_stat(PROC_STAT);
+        let total = sample.total_times.unwrap();
+        assert_eq!((total.user, total.nice, total.system, total.idle), (1000, 10, 300, 5000));
+        assert_eq!((total.iowait, total.irq, total.softirq, total.steal), (40, 0, 6, 0));
+        assert_eq!(sample.core_times.len(), 2);
+        assert_eq!(sample.core_times[&1].idle, 3000);
+        assert_eq!(sample.interrupts, 123456);
+        assert_eq!(sample.context_switches, 987654);
+        assert_eq!(sample.forks, 4321);
+        assert_eq!(sample.processes_running, 3);
+        assert_eq!(sample.processes_blocked, 1);
+    }
+
+    #[test]
+    fn test_parse_proc_stat_with_offline_core() {
+        let sample = parse_proc_stat("cpu  10 0 10 10 0 0 0 0\ncpu0 5 0 5 5 0 0 0 0\ncpu2 5 0 5 5 0 0 0 0\n");
+        assert!(sample.core_times.contains_key(&0));
+        assert!(!sample.core_times.contains_key(&1));
+        assert!(sample.core_times.contains_key(&2));
+    }
+
+    #[test]
+    fn test_parse_malformed_proc_stat() {
+        let sample = parse_proc_stat("\ncpu 10 abc\ncpux 1 2 3\nctxt\nprocesses many\nprocs_running 2\n");
+        let total = sample.total_times.unwrap();
+        // Missing and broken values are treated as 0
+        assert_eq!((total.user, total.nice, total.system, total.idle), (10, 0, 0, 0));
+        assert!(sample.core_times.is_empty());
+        assert_eq!(sample.context_switches, 0);
+        assert_eq!(sample.forks, 0);
+        assert_eq!(sample.processes_running, 2);
+    }
+
+    #[test]
+    fn test_cpu_states_from_times_difference() {
+        let previous = parse_cpu_times(&["100", "0", "100", "700", "100", "0", "0", "0"]);
+        let current = parse_cpu_times(&["180", "0", "140", "940", "100", "0", "0", "40"]);
+        let states = CpuStates::from_times_difference(&previous, &current).unwrap();
+        assert_eq!(states.user, 20.0);
+        assert_eq!(states.system, 10.0);
+        assert_eq!(states.idle, 60.0);
+        assert_eq!(states.iowait, 0.0);
+        assert_eq!(states.steal, 10.0);
+    }
+
+    #[test]
+    fn test_cpu_states_without_elapsed_time() {
+        let times = parse_cpu_times(&["100", "0", "100", "700"]);
+        assert!(CpuStates::from_times_difference(&times, &times).is_none());
+    }
+}
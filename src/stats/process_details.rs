@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use nix::unistd::{sysconf, SysconfVar};
+
+use crate::stats::read_uptime_seconds;
+use crate::stats::tasks::read_process_stat;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ProcessDetails {
+    pub threads: Option<f64>,
+    pub open_files: Option<f64>,
+    pub virtual_memory_bytes: Option<u64>,
+    pub voluntary_context_switches_per_second: Option<f64>,
+    pub involuntary_context_switches_per_second: Option<f64>,
+    pub read_bytes_per_second: Option<f64>,
+    pub write_bytes_per_second: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProcessDetailsSample {
+    threads: Option<u64>,
+    open_files: Option<u64>,
+    virtual_memory_bytes: Option<u64>,
+    voluntary_context_switches: Option<u64>,
+    involuntary_context_switches: Option<u64>,
+    read_bytes: Option<u64>,
+    write_bytes: Option<u64>,
+    time: Instant,
+}
+
+// Each tracked process keeps its own previous sample, because processes may be found or lost at any tick
+#[derive(Default, Debug, Clone)]
+pub struct ProcessDetailsTracker {
+    previous_sample: Option<ProcessDetailsSample>,
+    pub details: ProcessDetails,
+}
+
+impl ProcessDetailsTracker {
+    pub fn refresh(&mut self, pid: usize) {
+        let current = read_process_details_sample(pid);
+        let previous = self.previous_sample.as_ref();
+        let seconds = previous.map(|previous| current.time.duration_since(previous.time).as_secs_f64());
+        let rate = |current: Option<u64>, previous: Option<u64>| match (current, previous, seconds) {
+            (Some(current), Some(previous), Some(seconds)) if seconds > 0.0 => Some(current.saturating_sub(previous) as f64 / seconds),
+            _ => None,
+        };
+
+        self.details = ProcessDetails {
+            threads: current.threads.map(|e| e as f64),
+            open_files: current.open_files.map(|e| e as f64),
+            virtual_memory_bytes: current.virtual_memory_bytes,
+            voluntary_context_switches_per_second: rate(current.voluntary_context_switches, previous.and_then(|e| e.voluntary_context_switches)),
+            involuntary_context_switches_per_second: rate(
+                current.involuntary_context_switches,
+                previous.and_then(|e| e.involuntary_context_switches),
+            ),
+            read_bytes_per_second: rate(current.read_bytes, previous.and_then(|e| e.read_bytes)),
+            write_bytes_per_second: rate(current.write_bytes, previous.and_then(|e| e.write_bytes)),
+        };
+        self.previous_sample = Some(current);
+    }
+}
+
+// Cpu usage of tracked process, summed with its descendants when whole tree is tracked
+// Only pids of tracked processes are read, so cost does not depend on number of all processes
+#[derive(Default, Debug, Clone)]
+pub struct ProcessCpuTracker {
+    previous_cpu_times: HashMap<usize, u64>,
+    // Time since boot of previous refresh, in clock ticks
+    previous_uptime_ticks: Option<f64>,
+}
+
+impl ProcessCpuTracker {
+    // Returns usage summed for all pids, where 100% means one fully used core, None in first refresh
+    // Processes not seen in previous refresh are counted from zero only if they started after it, otherwise their whole
+    // cpu time since start would be counted as used in one tick, so they are skipped until next refresh
+    // Cpu time of processes which ended between ticks is lost
+    pub fn refresh(&mut self, pids: &[usize]) -> Option<f32> {
+        let ticks_per_second = sysconf(SysconfVar::CLK_TCK).ok().flatten().map_or(100.0, |e| e as f64);
+        let uptime_ticks = read_uptime_seconds().ok()? * ticks_per_second;
+        let process_stats = pids.iter().filter_map(|pid| Some((*pid, read_process_stat(*pid)?))).collect::<Vec<_>>();
+
+        let usage = self.previous_uptime_ticks.and_then(|previous_uptime_ticks| {
+            let elapsed_ticks = uptime_ticks - previous_uptime_ticks;
+            if elapsed_ticks <= 0.0 {
+                return None;
+            }
+            let used_ticks = process_stats
+                .iter()
+                .filter_map(|(pid, process_stat)| match self.previous_cpu_times.get(pid) {
+                    Some(previous_ticks) => Some(process_stat.cpu_time_ticks.saturating_sub(*previous_ticks)),
+                    None if process_stat.start_time_ticks as f64 >= previous_uptime_ticks => Some(process_stat.cpu_time_ticks),
+                    None => None,
+                })
+                .sum::<u64>();
+            Some((used_ticks as f64 / elapsed_ticks * 100.0) as f32)
+        });
+        self.previous_cpu_times = process_stats.into_iter().map(|(pid, e)| (pid, e.cpu_time_ticks)).collect();
+        self.previous_uptime_ticks = Some(uptime_ticks);
+        usage
+    }
+}
+
+// Files of processes owned by other users(fd directory, io) may not be readable without root privileges
+fn read_process_details_sample(pid: usize) -> ProcessDetailsSample {
+    let mut sample = ProcessDetailsSample {
+        threads: None,
+        open_files: None,
+        virtual_memory_bytes: None,
+        voluntary_context_switches: None,
+        involuntary_context_switches: None,
+        read_bytes: None,
+        write_bytes: None,
+        time: Instant::now(),
+    };
+
+    // Line looks like - VmSize:	    3340 kB
+    if let Ok(content) = fs::read_to_string(format!("/proc/{pid}/status")) {
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.split_whitespace().next().and_then(|e| e.parse::<u64>().ok());
+            match key {
+                "Threads" => sample.threads = value,
+                "VmSize" => sample.virtual_memory_bytes = value.map(|e| e * 1024),
+                "voluntary_ctxt_switches" => sample.voluntary_context_switches = value,
+                "nonvoluntary_ctxt_switches" => sample.involuntary_context_switches = value,
+                _ => {}
+            }
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(format!("/proc/{pid}/fd")) {
+        sample.open_files = Some(entries.count() as u64);
+    }
+
+    // Only bytes really read from/written to storage are used, rchar/wchar contains also e.g. reads from pipes
+    if let Ok(content) = fs::read_to_string(format!("/proc/{pid}/io")) {
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().parse::<u64>().ok();
+            match key {
+                "read_bytes" => sample.read_bytes = value,
+                "write_bytes" => sample.write_bytes = value,
+                _ => {}
+            }
+        }
+    }
+
+    sample
+}
+
+// All values are proportional, so memory shared between processes is split between them
+// Anonymous, file and shmem parts sum up to pss, swap is not included in pss
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SmapsRollup {
+    pub pss_bytes: Option<u64>,
+    pub pss_anon_bytes: Option<u64>,
+    pub pss_file_bytes: Option<u64>,
+    pub pss_shmem_bytes: Option<u64>,
+    pub swap_pss_bytes: Option<u64>,
+}
+
+// Line looks like - Pss_Anon:            104 kB
+// Pss_Anon, Pss_File and Pss_Shmem are available since Linux 5.7
+pub fn read_smaps_rollup(pid: usize) -> Option<SmapsRollup> {
+    let content = fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?;
+
+    let mut smaps_rollup = SmapsRollup::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.split_whitespace().next().and_then(|e| e.parse::<u64>().ok()).map(|e| e * 1024);
+        match key {
+            "Pss" => smaps_rollup.pss_bytes = value,
+            "Pss_Anon" => smaps_rollup.pss_anon_bytes = value,
+            "Pss_File" => smaps_rollup.pss_file_bytes = value,
+            "Pss_Shmem" => smaps_rollup.pss_shmem_bytes = value,
+            "SwapPss" => smaps_rollup.swap_pss_bytes = value,
+            _ => {}
+        }
+    }
+    Some(smaps_rollup)
+}
@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::enums::ProcessEventKind;
+use crate::model::ProcessSelector;
+use crate::stats::processes::{ProcessEntry, ProcessesStatsCache};
+use crate::stats::{read_trimmed, sanitize_item_name};
+
+#[derive(Debug, Clone)]
+pub struct ProcessEvent {
+    pub kind: ProcessEventKind,
+    pub pid: usize,
+    pub ppid: usize,
+    pub name: String,
+    pub cmd: String,
+    // Known only when process exited
+    pub lifetime_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+struct WatchedProcess {
+    ppid: usize,
+    name: String,
+    cmd: String,
+    start_time_ticks: u64,
+}
+
+// Processes which started and ended between ticks are not visible here
+#[derive(Debug, Clone)]
+pub struct ProcessEventsStatsCache {
+    // Empty means that all processes are watched
+    filters: Vec<ProcessSelector>,
+    // All pids found in previous tick, also not matching filters, to not check them again
+    previous_pids: HashSet<usize>,
+    watched_processes: HashMap<usize, WatchedProcess>,
+    // Events found in last tick
+    pub events: Vec<ProcessEvent>,
+}
+
+impl ProcessEventsStatsCache {
+    // Already running processes don't create start events, but their exit is still saved
+    pub fn new(filters: &[ProcessSelector], processes: &ProcessesStatsCache) -> Self {
+        let mut process_events_stats = ProcessEventsStatsCache {
+            filters: filters.to_vec(),
+            previous_pids: HashSet::new(),
+            watched_processes: HashMap::new(),
+            events: Vec::new(),
+        };
+        process_events_stats.refresh(processes);
+        process_events_stats.events.clear();
+        process_events_stats
+    }
+
+    pub fn refresh(&mut self, processes: &ProcessesStatsCache) {
+        let system_pids = processes.pids();
+        self.events.clear();
+
+        let mut exited_pids = self
+            .watched_processes
+            .keys()
+            .filter(|pid| !system_pids.contains(pid))
+            .copied()
+            .collect::<Vec<_>>();
+        exited_pids.sort_unstable();
+        let uptime_seconds = processes.uptime_seconds();
+        for pid in exited_pids {
+            let Some(process) = self.watched_processes.remove(&pid) else {
+                continue;
+            };
+            self.events.push(ProcessEvent {
+                kind: ProcessEventKind::EXITED,
+                pid,
+                ppid: process.ppid,
+                name: process.name,
+                cmd: process.cmd,
+                lifetime_seconds: Some((uptime_seconds - process.start_time_ticks as f64 / processes.ticks_per_second).max(0.0)),
+            });
+        }
+
+        let mut started_pids = system_pids.difference(&self.previous_pids).copied().collect::<Vec<_>>();
+        started_pids.sort_unstable();
+        for pid in started_pids {
+            // Process may end between reading list of pids and reading its files, then it is just skipped
+            let Some(process) = self.read_watched_process(pid, &processes.processes[&pid]) else {
+                continue;
+            };
+            self.events.push(ProcessEvent {
+                kind: ProcessEventKind::STARTED,
+                pid,
+                ppid: process.ppid,
+                name: process.name.clone(),
+                cmd: process.cmd.clone(),
+                lifetime_seconds: None,
+            });
+            self.watched_processes.insert(pid, process);
+        }
+
+        self.previous_pids = system_pids;
+    }
+
+    // Kernel threads are skipped, because they are created and removed by kernel all the time
+    fn read_watched_process(&self, pid: usize, process: &ProcessEntry) -> Option<WatchedProcess> {
+        if pid == 2 || process.stat.ppid == 2 {
+            return None;
+        }
+        let name = read_trimmed(Path::new(&format!("/proc/{pid}/comm")))?;
+        // Arguments are split by null bytes, they may also contain new lines
+        let cmd = fs::read(format!("/proc/{pid}/cmdline"))
+            .map(|e| String::from_utf8_lossy(&e).replace(['\0', '\n', '\r'], " ").trim().to_string())
+            .unwrap_or_default();
+
+        if !self.filters.is_empty() {
+            // Exe and cwd of processes owned by other users may not be readable without root privileges
+            let exe = fs::read_link(format!("/proc/{pid}/exe")).ok();
+            let cwd = fs::read_link(format!("/proc/{pid}/cwd")).ok();
+            if !self
+                .filters
+                .iter()
+                .any(|filter| filter.matches_values(pid, &name, exe.as_deref(), process.uid, cwd.as_deref(), &cmd))
+            {
+                return None;
+            }
+        }
+
+        Some(WatchedProcess {
+            ppid: process.stat.ppid,
+            name: sanitize_item_name(&name),
+            cmd,
+            start_time_ticks: process.stat.start_time_ticks,
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+
+use anyhow::Error;
+use nix::unistd::{sysconf, SysconfVar};
+
+use crate::collector::get_system_pids;
+use crate::stats::read_uptime_seconds;
+#[cfg(test)]
+use crate::stats::tasks::parse_process_stat;
+use crate::stats::tasks::{read_process_stat, ProcessStat};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ProcessEntry {
+    pub stat: ProcessStat,
+    // Owner of /proc/<pid> directory, read only when owners are needed
+    pub uid: Option<u32>,
+}
+
+// All processes are read once per tick and shared by stats which need data of many processes(tasks, users, top
+// processes, process events and descendants of tracked process trees), so /proc is walked only once
+#[derive(Debug, Clone)]
+pub struct ProcessesStatsCache {
+    pub ticks_per_second: f64,
+    pub page_size: u64,
+    // Processes which ended between reading list of pids and reading their stat file are not here
+    pub processes: HashMap<usize, ProcessEntry>,
+    read_owners: bool,
+    // Time since boot, in clock ticks
+    uptime_ticks: f64,
+    // None before first refresh, so there is nothing to compare with
+    previous_uptime_ticks: Option<f64>,
+    // Keyed also by start time, so process which reused pid of ended process is not compared with it
+    previous_cpu_times: HashMap<(usize, u64), u64>,
+}
+
+impl ProcessesStatsCache {
+    pub fn new(read_owners: bool) -> Result<Self, Error> {
+        let mut processes_stats = ProcessesStatsCache {
+            ticks_per_second: sysconf(SysconfVar::CLK_TCK).ok().flatten().map_or(100.0, |e| e as f64),
+            page_size: sysconf(SysconfVar::PAGE_SIZE).ok().flatten().map_or(4096, |e| e as u64),
+            processes: HashMap::new(),
+            read_owners,
+            uptime_ticks: 0.0,
+            previous_uptime_ticks: None,
+            previous_cpu_times: HashMap::new(),
+        };
+        processes_stats.read_processes()?;
+        Ok(processes_stats)
+    }
+
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        self.save_previous_values();
+        self.read_processes()
+    }
+
+    fn save_previous_values(&mut self) {
+        self.previous_cpu_times = self
+            .processes
+            .iter()
+            .map(|(pid, e)| ((*pid, e.stat.start_time_ticks), e.stat.cpu_time_ticks))
+            .collect();
+        self.previous_uptime_ticks = Some(self.uptime_ticks);
+    }
+
+    fn read_processes(&mut self) -> Result<(), Error> {
+        self.uptime_ticks = read_uptime_seconds()? * self.ticks_per_second;
+        self.processes = get_system_pids()?
+            .into_iter()
+            .filter_map(|pid| {
+                let stat = read_process_stat(pid)?;
+                let uid = if self.read_owners {
+                    fs::metadata(format!("/proc/{pid}")).ok().map(|e| e.uid())
+                } else {
+                    None
+                };
+                Some((pid, ProcessEntry { stat, uid }))
+            })
+            .collect();
+        Ok(())
+    }
+
+    pub fn pids(&self) -> HashSet<usize> {
+        self.processes.keys().copied().collect()
+    }
+
+    pub fn uptime_seconds(&self) -> f64 {
+        self.uptime_ticks / self.ticks_per_second
+    }
+
+    pub fn elapsed_seconds(&self) -> Option<f64> {
+        let elapsed_ticks = self.uptime_ticks - self.previous_uptime_ticks?;
+        (elapsed_ticks > 0.0).then(|| elapsed_ticks / self.ticks_per_second)
+    }
+
+    // Processes not seen in previous refresh are counted from zero only if they started after it, otherwise their
+    // whole cpu time since start would be counted as used in one tick, so they are skipped until next refresh
+    // Cpu time of processes which ended between refreshes is lost
+    pub fn used_ticks(&self, pid: usize) -> Option<u64> {
+        let previous_uptime_ticks = self.previous_uptime_ticks?;
+        let process = self.processes.get(&pid)?;
+        match self.previous_cpu_times.get(&(pid, process.stat.start_time_ticks)) {
+            Some(previous_ticks) => Some(process.stat.cpu_time_ticks.saturating_sub(*previous_ticks)),
+            None if process.stat.start_time_ticks as f64 >= previous_uptime_ticks => Some(process.stat.cpu_time_ticks),
+            None => None,
+        }
+    }
+
+    // 100% means one fully used core
+    pub fn cpu_usage_percent(&self, used_ticks: u64, elapsed_seconds: f64) -> f64 {
+        used_ticks as f64 / self.ticks_per_second / elapsed_seconds * 100.0
+    }
+
+    // Children are read from parent pid of every process, because /proc/<pid>/task/<tid>/children may not be available
+    pub fn children(&self) -> HashMap<usize, Vec<usize>> {
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (pid, process) in &self.processes {
+            children.entry(process.stat.ppid).or_default().push(*pid);
+        }
+        children
+    }
+}
+
+// Content of /proc/<pid>/stat with values used by app, user and system time are split equally
+#[cfg(test)]
+pub fn fake_stat_file(pid: usize, comm: &str, state: char, ppid: usize, cpu_time_ticks: u64, start_time_ticks: u64, rss_pages: u64) -> String {
+    format!(
+        "{pid} ({comm}) {state} {ppid} {pid} {pid} 0 -1 4194560 1000 0 10 0 {} {} 0 0 20 0 3 0 {start_time_ticks} 123456789 {rss_pages} 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 3 0 0 0 0 0",
+        cpu_time_ticks / 2,
+        cpu_time_ticks - cpu_time_ticks / 2
+    )
+}
+
+// Snapshot created from content of /proc/<pid>/stat files instead of /proc, with 100 ticks per second and 4096 bytes pages
+#[cfg(test)]
+impl ProcessesStatsCache {
+    pub fn from_stat_files(stat_files: &[(usize, &str)], uptime_seconds: f64) -> Self {
+        let mut processes_stats = ProcessesStatsCache {
+            ticks_per_second: 100.0,
+            page_size: 4096,
+            processes: HashMap::new(),
+            read_owners: false,
+            uptime_ticks: 0.0,
+            previous_uptime_ticks: None,
+            previous_cpu_times: HashMap::new(),
+        };
+        processes_stats.set_stat_files(stat_files, uptime_seconds);
+        processes_stats
+    }
+
+    pub fn refresh_from_stat_files(&mut self, stat_files: &[(usize, &str)], uptime_seconds: f64) {
+        self.save_previous_values();
+        self.set_stat_files(stat_files, uptime_seconds);
+    }
+
+    fn set_stat_files(&mut self, stat_files: &[(usize, &str)], uptime_seconds: f64) {
+        self.uptime_ticks = uptime_seconds * self.ticks_per_second;
+        self.processes = stat_files
+            .iter()
+            .map(|(pid, content)| {
+                (
+                    *pid,
+                    ProcessEntry {
+                        stat: parse_process_stat(content).unwrap(),
+                        uid: None,
+                    },
+                )
+            })
+            .collect();
+    }
+}
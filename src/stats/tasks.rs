@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::stats::processes::ProcessesStatsCache;
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ProcessStat {
+    pub state: char,
+    pub ppid: usize,
+    // User and system time, in clock ticks
+    pub cpu_time_ticks: u64,
+    pub num_threads: u64,
+    // Time after system boot, in clock ticks
+    pub start_time_ticks: u64,
+    pub rss_pages: u64,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct TasksStatsCache {
+    pub processes: u64,
+    pub threads: u64,
+    pub running: u64,
+    pub sleeping: u64,
+    pub disk_sleep: u64,
+    pub zombie: u64,
+}
+
+impl TasksStatsCache {
+    pub fn new(processes: &ProcessesStatsCache) -> Self {
+        let mut tasks_stats = TasksStatsCache::default();
+        tasks_stats.refresh(processes);
+        tasks_stats
+    }
+
+    pub fn refresh(&mut self, processes: &ProcessesStatsCache) {
+        *self = TasksStatsCache::default();
+        for process in processes.processes.values() {
+            self.processes += 1;
+            self.threads += process.stat.num_threads;
+            match process.stat.state {
+                'R' => self.running += 1,
+                'S' => self.sleeping += 1,
+                'D' => self.disk_sleep += 1,
+                'Z' => self.zombie += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+// Returns all children, grandchildren etc. of process, without process itself
+pub fn find_descendants(pid: usize, children: &HashMap<usize, Vec<usize>>) -> Vec<usize> {
+    let mut descendants = Vec::new();
+    let mut to_check = vec![pid];
+    while let Some(pid) = to_check.pop() {
+        if let Some(process_children) = children.get(&pid) {
+            descendants.extend(process_children);
+            to_check.extend(process_children);
+        }
+    }
+    descendants
+}
+
+// Line looks like - 1 (systemd) S 0 1 1 0 -1 4194560 ...
+// Process name may contain spaces and brackets, so values are read after last bracket
+pub fn read_process_stat(pid: usize) -> Option<ProcessStat> {
+    parse_process_stat(&fs::read_to_string(format!("/proc/{pid}/stat")).ok()?)
+}
+
+pub fn parse_process_stat(content: &str) -> Option<ProcessStat> {
+    let (_, values) = content.rsplit_once(')')?;
+    let split = values.split_whitespace().collect::<Vec<_>>();
+
+    Some(ProcessStat {
+        state: split.first()?.chars().next()?,
+        ppid: split.get(1)?.parse().ok()?,
+        cpu_time_ticks: split.get(11)?.parse::<u64>().ok()? + split.get(12)?.parse::<u64>().ok()?,
+        num_threads: split.get(17)?.parse().ok()?,
+        start_time_ticks: split.get(19)?.parse().ok()?,
+        rss_pages: split.get(21)?.parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::processes::fake_stat_file;
+
+    #[test]
+    fn test_parse_process_stat() {
+        let content = "1234 (bash) S 1200 1234 1234 34816 5678 4194304 2000 30000 5 40 150 75 300 100 20 0 3 0 98765 23456789 1024 18446744073709551615 1 1 0 0 0 0 65536 3670020 1266777851 0 0 0 17 2 0 0 0 0 0\n";
+        let stat = parse_process_stat(content).unwrap();
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1200);
+        // Only own user and system time, without time of waited children
+        assert_eq!(stat.cpu_time_ticks, 225);
+        assert_eq!(stat.num_threads, 3);
+        assert_eq!(stat.start_time_ticks, 98765);
+        assert_eq!(stat.rss_pages, 1024);
+    }
+
+    #[test]
+    fn test_parse_process_stat_with_spaces_and_brackets_in_name() {
+        for comm in ["Web Content", "a) R 1 2 3 (b", ")", "((x))", " "] {
+            let content = fake_stat_file(50, comm, 'D', 7, 11, 400, 8);
+            let stat = parse_process_stat(&content).unwrap_or_else(|| panic!("Failed to parse {content}"));
+            assert_eq!(stat.state, 'D', "{comm}");
+            assert_eq!(stat.ppid, 7, "{comm}");
+            assert_eq!(stat.cpu_time_ticks, 11, "{comm}");
+            assert_eq!(stat.start_time_ticks, 400, "{comm}");
+            assert_eq!(stat.rss_pages, 8, "{comm}");
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_process_stat() {
+        let content = fake_stat_file(50, "app", 'S', 7, 11, 400, 8);
+        let without_rss = content.split(' ').take(23).collect::<Vec<_>>().join(" ");
+        let broken_ppid = content.replacen(" 7 ", " x ", 1);
+        for content in ["", "50 app S 7", "50 (app)", without_rss.as_str(), broken_ppid.as_str()] {
+            assert!(parse_process_stat(content).is_none(), "{content}");
+        }
+    }
+
+    #[test]
+    fn test_find_descendants() {
+        let children = HashMap::from([(1, vec![10, 11]), (10, vec![100]), (100, vec![1000]), (2, vec![20])]);
+        let mut descendants = find_descendants(1, &children);
+        descendants.sort_unstable();
+        assert_eq!(descendants, vec![10, 11, 100, 1000]);
+        assert!(find_descendants(1000, &children).is_empty());
+    }
+
+    #[test]
+    fn test_count_tasks() {
+        let stat_files = [
+            (1, fake_stat_file(1, "init", 'S', 0, 0, 1, 1)),
+            (2, fake_stat_file(2, "worker", 'R', 1, 0, 1, 1)),
+            (3, fake_stat_file(3, "dd", 'D', 1, 0, 1, 1)),
+            (4, fake_stat_file(4, "defunct", 'Z', 1, 0, 1, 1)),
+            (5, fake_stat_file(5, "stopped", 'T', 1, 0, 1, 1)),
+        ];
+        let stat_files = stat_files.iter().map(|(pid, content)| (*pid, content.as_str())).collect::<Vec<_>>();
+        let tasks = TasksStatsCache::new(&ProcessesStatsCache::from_stat_files(&stat_files, 10.0));
+        assert_eq!(tasks.processes, 5);
+        // Every fake process has 3 threads
+        assert_eq!(tasks.threads, 15);
+        assert_eq!((tasks.running, tasks.sleeping, tasks.disk_sleep, tasks.zombie), (1, 1, 1, 1));
+    }
+}
@@ -0,0 +1,161 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct TcpStates {
+    pub established: u64,
+    pub syn_sent: u64,
+    pub syn_recv: u64,
+    pub fin_wait1: u64,
+    pub fin_wait2: u64,
+    pub time_wait: u64,
+    pub close: u64,
+    pub close_wait: u64,
+    pub last_ack: u64,
+    pub listen: u64,
+    pub closing: u64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TcpStatesStatsCache {
+    // Empty means that sockets with all local ports are counted
+    pub local_ports: Vec<u16>,
+    // None if neither /proc/net/tcp nor /proc/net/tcp6 could be read
+    pub states: Option<TcpStates>,
+}
+
+impl TcpStatesStatsCache {
+    pub fn new(local_ports: &[u16]) -> Self {
+        let mut tcp_states_stats = TcpStatesStatsCache {
+            local_ports: local_ports.to_vec(),
+            states: None,
+        };
+        tcp_states_stats.refresh();
+        tcp_states_stats
+    }
+
+    pub fn refresh(&mut self) {
+        let mut states = TcpStates::default();
+        let mut any_file_read = false;
+        for file_name in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            any_file_read |= count_tcp_states(file_name, &self.local_ports, &mut states);
+        }
+        self.states = any_file_read.then_some(states);
+    }
+}
+
+// Line looks like - 0: 0100007F:0CEA 00000000:0000 0A 00000000:00000000 00:00000000 00000000 0 0 662 1 ...
+// Port and state are saved as hex numbers
+// With many connections, file may be really big, so it is read line by line
+fn count_tcp_states(file_name: &str, local_ports: &[u16], states: &mut TcpStates) -> bool {
+    let Ok(file) = File::open(file_name) else {
+        return false;
+    };
+    count_tcp_states_in_reader(BufReader::new(file), local_ports, states);
+    true
+}
+
+fn count_tcp_states_in_reader(reader: impl BufRead, local_ports: &[u16], states: &mut TcpStates) {
+    for line in reader.lines().skip(1).map_while(Result::ok) {
+        let mut split = line.split_whitespace();
+        let (Some(local_address), Some(state)) = (split.nth(1), split.nth(1)) else {
+            continue;
+        };
+        if !local_ports.is_empty() {
+            let Some(port) = local_address.rsplit_once(':').and_then(|(_, port)| u16::from_str_radix(port, 16).ok()) else {
+                continue;
+            };
+            if !local_ports.contains(&port) {
+                continue;
+            }
+        }
+
+        let counter = match u8::from_str_radix(state, 16) {
+            Ok(0x01) => &mut states.established,
+            Ok(0x02) => &mut states.syn_sent,
+            Ok(0x03) => &mut states.syn_recv,
+            Ok(0x04) => &mut states.fin_wait1,
+            Ok(0x05) => &mut states.fin_wait2,
+            Ok(0x06) => &mut states.time_wait,
+            Ok(0x07) => &mut states.close,
+            Ok(0x08) => &mut states.close_wait,
+            Ok(0x09) => &mut states.last_ack,
+            Ok(0x0A) => &mut states.listen,
+            Ok(0x0B) => &mut states.closing,
+            _ => continue,
+        };
+        *counter += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROC_NET_TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0CEA 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 0000000000000000 100 0 0 10 0
+   1: 00000000:01BB 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 663 1 0000000000000000 100 0 0 10 0
+   2: 0A00000F:01BB 0A000001:D431 01 00000000:00000000 02:000A7D6B 00000000     0        0 664 2 0000000000000000 20 4 30 10 -1
+   3: 0A00000F:01BB 0A000002:D432 01 00000000:00000000 02:000A7D6B 00000000     0        0 665 2 0000000000000000 20 4 30 10 -1
+   4: 0A00000F:9C40 0A000003:0050 06 00000000:00000000 03:00000F6E 00000000     0        0 0 3 0000000000000000
+   5: 0A00000F:01BB 0A000004:D433 08 00000000:00000000 00:00000000 00000000     0        0 666 1 0000000000000000 20 4 30 10 -1
+";
+
+    fn count(content: &str, local_ports: &[u16]) -> TcpStates {
+        let mut states = TcpStates::default();
+        count_tcp_states_in_reader(content.as_bytes(), local_ports, &mut states);
+        states
+    }
+
+    #[test]
+    fn test_count_all_tcp_states() {
+        let states = count(PROC_NET_TCP, &[]);
+        assert_eq!(states.listen, 2);
+        assert_eq!(states.established, 2);
+        assert_eq!(states.time_wait, 1);
+        assert_eq!(states.close_wait, 1);
+        assert_eq!(states.syn_sent, 0);
+    }
+
+    #[test]
+    fn test_count_tcp_states_of_local_ports() {
+        // 0x01BB is 443
+        let states = count(PROC_NET_TCP, &[443]);
+        assert_eq!(states.listen, 1);
+        assert_eq!(states.established, 2);
+        assert_eq!(states.time_wait, 0);
+        assert_eq!(states.close_wait, 1);
+    }
+
+    #[test]
+    fn test_count_tcp6_states() {
+        let content = "  sl  local_address                         remote_address                        st tx_queue rx_queue
+   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 667 1
+   1: 0000000000000000FFFF00000F00000A:0016 0000000000000000FFFF00000100000A:D434 01 00000000:00000000 02:00000000 00000000     0        0 668 1
+";
+        let states = count(content, &[22]);
+        assert_eq!(states.listen, 1);
+        assert_eq!(states.established, 1);
+    }
+
+    #[test]
+    fn test_malformed_lines_are_skipped() {
+        let content = "header
+   0: 0100007F:0CEA
+   1: 0100007F:0CEA 00000000:0000 ZZ 00000000:00000000
+   2: 0100007F:0CEA 00000000:0000 0C 00000000:00000000
+   3: 0100007F 00000000:0000 01 00000000:00000000
+
+   4: 0100007F:0CEA 00000000:0000 0B 00000000:00000000
+";
+        let states = count(content, &[]);
+        // Line without port in local address is counted only when ports are not filtered
+        assert_eq!(states.established, 1);
+        assert_eq!(states.closing, 1);
+        assert_eq!(states.listen, 0);
+
+        let states = count(content, &[0x0CEA]);
+        assert_eq!(states.established, 0);
+        assert_eq!(states.closing, 1);
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::stats::{read_sorted_dir, read_trimmed};
+
+#[derive(Default, Debug, Clone)]
+pub struct TemperatureSensor {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TemperatureStatsCache {
+    // Sensors found when app started, only they are tracked
+    pub sensors: Vec<TemperatureSensor>,
+    // None if sensor cannot be read anymore
+    pub temperatures: Vec<Option<f64>>,
+}
+
+impl TemperatureStatsCache {
+    pub fn new(sysfs_path: &str) -> Self {
+        let sysfs_path = Path::new(sysfs_path);
+        let mut sensors = find_thermal_zone_sensors(sysfs_path);
+        sensors.extend(find_hwmon_sensors(sysfs_path));
+
+        let mut temperature_stats = TemperatureStatsCache {
+            sensors,
+            temperatures: Vec::new(),
+        };
+        temperature_stats.refresh();
+        temperature_stats
+    }
+
+    pub fn refresh(&mut self) {
+        self.temperatures = self.sensors.iter().map(|sensor| read_temperature(&sensor.path)).collect();
+    }
+
+    pub fn get_sensor_names(&self) -> Vec<String> {
+        self.sensors.iter().map(|sensor| sensor.name.clone()).collect()
+    }
+}
+
+// Kernel provides temperature in millidegrees Celsius
+fn read_temperature(path: &Path) -> Option<f64> {
+    let content = fs::read_to_string(path).ok()?;
+    content.trim().parse::<i64>().ok().map(|millidegrees| millidegrees as f64 / 1000.0)
+}
+
+// Each zone is saved as e.g. thermal_zone0_x86_pkg_temp
+fn find_thermal_zone_sensors(sysfs_path: &Path) -> Vec<TemperatureSensor> {
+    let mut sensors = Vec::new();
+    for zone_path in read_sorted_dir(&sysfs_path.join("class/thermal")) {
+        let zone_name = zone_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if !zone_name.starts_with("thermal_zone") || !zone_path.join("temp").exists() {
+            continue;
+        }
+        let name = match read_trimmed(&zone_path.join("type")) {
+            Some(zone_type) => format!("{zone_name}_{zone_type}"),
+            None => zone_name,
+        };
+        sensors.push(TemperatureSensor {
+            name,
+            path: zone_path.join("temp"),
+        });
+    }
+    sensors
+}
+
+// Each sensor is saved as e.g. coretemp_Core 0 or nvme_temp1 when there is no label
+fn find_hwmon_sensors(sysfs_path: &Path) -> Vec<TemperatureSensor> {
+    let mut sensors = Vec::new();
+    for hwmon_path in read_sorted_dir(&sysfs_path.join("class/hwmon")) {
+        let hwmon_name =
+            read_trimmed(&hwmon_path.join("name")).unwrap_or_else(|| hwmon_path.file_name().unwrap_or_default().to_string_lossy().to_string());
+        for input_path in read_sorted_dir(&hwmon_path) {
+            let file_name = input_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let Some(sensor_id) = file_name.strip_prefix("temp").and_then(|e| e.strip_suffix("_input")) else {
+                continue;
+            };
+            let label = read_trimmed(&hwmon_path.join(format!("temp{sensor_id}_label"))).unwrap_or_else(|| format!("temp{sensor_id}"));
+            sensors.push(TemperatureSensor {
+                name: format!("{hwmon_name}_{label}"),
+                path: input_path,
+            });
+        }
+    }
+    sensors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_thermal_zones_and_hwmon_sensors() {
+        let test_dir = TestDir::new("temperature");
+        test_dir.write("class/thermal/thermal_zone0/type", "x86_pkg_temp\n");
+        test_dir.write("class/thermal/thermal_zone0/temp", "45000\n");
+        test_dir.write("class/thermal/thermal_zone1/temp", "-5500\n");
+        test_dir.write("class/thermal/cooling_device0/type", "Processor\n");
+        test_dir.write("class/hwmon/hwmon0/name", "coretemp\n");
+        test_dir.write("class/hwmon/hwmon0/temp1_input", "52000\n");
+        test_dir.write("class/hwmon/hwmon0/temp1_label", "Core 0\n");
+        test_dir.write("class/hwmon/hwmon0/temp2_input", "53000\n");
+        test_dir.write("class/hwmon/hwmon0/temp1_max", "100000\n");
+        test_dir.write("class/hwmon/hwmon1/temp1_input", "38000\n");
+
+        let temperature_stats = TemperatureStatsCache::new(test_dir.path_str());
+        assert_eq!(
+            temperature_stats.get_sensor_names(),
+            vec![
+                "thermal_zone0_x86_pkg_temp",
+                "thermal_zone1",
+                "coretemp_Core 0",
+                "coretemp_temp2",
+                "hwmon1_temp1"
+            ]
+        );
+        assert_eq!(
+            temperature_stats.temperatures,
+            vec![Some(45.0), Some(-5.5), Some(52.0), Some(53.0), Some(38.0)]
+        );
+    }
+
+    #[test]
+    fn test_malformed_and_disappeared_sensors() {
+        let test_dir = TestDir::new("temperature");
+        test_dir.write("class/thermal/thermal_zone0/temp", "hot\n");
+        test_dir.write("class/thermal/thermal_zone1/temp", "41000\n");
+        test_dir.write("class/thermal/thermal_zone2/temp", "\n");
+
+        let mut temperature_stats = TemperatureStatsCache::new(test_dir.path_str());
+        assert_eq!(temperature_stats.temperatures, vec![None, Some(41.0), None]);
+
+        fs::remove_file(test_dir.path.join("class/thermal/thermal_zone1/temp")).unwrap();
+        temperature_stats.refresh();
+        assert_eq!(temperature_stats.temperatures, vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_missing_sysfs() {
+        let test_dir = TestDir::new("temperature");
+        let temperature_stats = TemperatureStatsCache::new(test_dir.path_str());
+        assert!(temperature_stats.sensors.is_empty());
+        assert!(temperature_stats.temperatures.is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::stats::processes::ProcessesStatsCache;
+use crate::stats::{read_trimmed, sanitize_item_name};
+
+#[derive(Default, Debug, Clone)]
+pub struct TopProcessUsage {
+    pub pid: usize,
+    pub name: String,
+    pub cpu_usage_percent: f64,
+    pub memory_bytes: u64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TopProcessesSnapshot {
+    pub top_cpu: Vec<TopProcessUsage>,
+    pub top_memory: Vec<TopProcessUsage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TopProcessesStatsCache {
+    top_processes: usize,
+    every_ticks: usize,
+    ticks_since_snapshot: usize,
+    // Cpu usage is averaged over all ticks since previous snapshot, so short spikes between snapshots are not lost
+    seconds_since_snapshot: f64,
+    // Keyed by pid and start time, so process which reused pid of ended process doesn't inherit its usage
+    used_ticks_since_snapshot: HashMap<(usize, u64), u64>,
+    core_count: usize,
+    // Available only in ticks in which snapshot was taken
+    pub snapshot: Option<TopProcessesSnapshot>,
+}
+
+impl TopProcessesStatsCache {
+    pub fn new(top_processes: usize, every_ticks: usize, core_count: usize) -> Self {
+        TopProcessesStatsCache {
+            top_processes,
+            every_ticks: every_ticks.max(1),
+            ticks_since_snapshot: 0,
+            seconds_since_snapshot: 0.0,
+            used_ticks_since_snapshot: HashMap::new(),
+            core_count,
+            snapshot: None,
+        }
+    }
+
+    pub fn refresh(&mut self, processes: &ProcessesStatsCache) {
+        self.ticks_since_snapshot += 1;
+        self.seconds_since_snapshot += processes.elapsed_seconds().unwrap_or(0.0);
+        for (pid, process) in &processes.processes {
+            *self.used_ticks_since_snapshot.entry((*pid, process.stat.start_time_ticks)).or_default() += processes.used_ticks(*pid).unwrap_or(0);
+        }
+        if self.ticks_since_snapshot < self.every_ticks {
+            self.snapshot = None;
+            return;
+        }
+
+        let mut usages = self.read_usages(processes);
+        self.ticks_since_snapshot = 0;
+        self.seconds_since_snapshot = 0.0;
+        self.used_ticks_since_snapshot.clear();
+
+        // Pid is used as last key, to always have the same order of processes with equal usage
+        usages.sort_by(|a, b| b.cpu_usage_percent.total_cmp(&a.cpu_usage_percent).then(a.pid.cmp(&b.pid)));
+        let top_cpu = usages.iter().take(self.top_processes).cloned().collect::<Vec<_>>();
+        usages.sort_by(|a, b| b.memory_bytes.cmp(&a.memory_bytes).then(a.pid.cmp(&b.pid)));
+        let top_memory = usages.into_iter().take(self.top_processes).collect::<Vec<_>>();
+
+        // Names are read only for processes which are saved, because reading them for all processes would be wasteful
+        let mut snapshot = TopProcessesSnapshot { top_cpu, top_memory };
+        for process in snapshot.top_cpu.iter_mut().chain(snapshot.top_memory.iter_mut()) {
+            process.name =
+                read_trimmed(Path::new(&format!("/proc/{}/comm", process.pid))).map_or_else(|| "-".to_string(), |e| sanitize_item_name(&e));
+        }
+        self.snapshot = Some(snapshot);
+    }
+
+    // Only processes still running are used, processes which ended since previous snapshot are skipped
+    // 100% means that all cores are fully used, the same as for tracked processes
+    fn read_usages(&self, processes: &ProcessesStatsCache) -> Vec<TopProcessUsage> {
+        processes
+            .processes
+            .iter()
+            .map(|(pid, process)| {
+                let used_ticks = self
+                    .used_ticks_since_snapshot
+                    .get(&(*pid, process.stat.start_time_ticks))
+                    .copied()
+                    .unwrap_or(0);
+                TopProcessUsage {
+                    pid: *pid,
+                    name: String::new(),
+                    cpu_usage_percent: if self.seconds_since_snapshot > 0.0 {
+                        processes.cpu_usage_percent(used_ticks, self.seconds_since_snapshot) / self.core_count as f64
+                    } else {
+                        0.0
+                    },
+                    memory_bytes: process.stat.rss_pages * processes.page_size,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::processes::fake_stat_file;
+
+    // Pids are higher than maximum pid in linux, so they never have comm file in /proc
+    const PID_A: usize = 5_000_001;
+    const PID_B: usize = 5_000_002;
+    const PID_C: usize = 5_000_003;
+    const PID_D: usize = 5_000_004;
+    const PID_E: usize = 5_000_005;
+
+    // Values are pid, cpu time, start time and rss pages
+    fn stat_files(processes: &[(usize, u64, u64, u64)]) -> Vec<(usize, String)> {
+        processes
+            .iter()
+            .map(|(pid, cpu_time_ticks, start_time_ticks, rss_pages)| {
+                (
+                    *pid,
+                    fake_stat_file(*pid, "app name", 'S', 1, *cpu_time_ticks, *start_time_ticks, *rss_pages),
+                )
+            })
+            .collect()
+    }
+
+    fn as_stat_files(processes: &[(usize, String)]) -> Vec<(usize, &str)> {
+        processes.iter().map(|(pid, content)| (*pid, content.as_str())).collect()
+    }
+
+    fn pids(usages: &[TopProcessUsage]) -> Vec<usize> {
+        usages.iter().map(|e| e.pid).collect()
+    }
+
+    #[test]
+    fn test_top_processes_are_averaged_since_previous_snapshot() {
+        let first = stat_files(&[(PID_A, 100, 0, 1), (PID_B, 0, 0, 300), (PID_C, 500, 0, 1000)]);
+        let mut processes = ProcessesStatsCache::from_stat_files(&as_stat_files(&first), 100.0);
+        let mut top_processes = TopProcessesStatsCache::new(2, 2, 2);
+
+        // Process D started after previous refresh, so its whole cpu time is used
+        let second = stat_files(&[(PID_A, 150, 0, 1), (PID_B, 0, 0, 300), (PID_C, 500, 0, 1000), (PID_D, 20, 10_050, 300)]);
+        processes.refresh_from_stat_files(&as_stat_files(&second), 101.0);
+        top_processes.refresh(&processes);
+        assert!(top_processes.snapshot.is_none());
+
+        // Process E was running before previous refresh, but was not seen, so its cpu time is unknown
+        let third = stat_files(&[
+            (PID_A, 250, 0, 1),
+            (PID_B, 0, 0, 300),
+            (PID_C, 600, 0, 1000),
+            (PID_D, 20, 10_050, 300),
+            (PID_E, 10_000, 500, 5),
+        ]);
+        processes.refresh_from_stat_files(&as_stat_files(&third), 102.0);
+        top_processes.refresh(&processes);
+        let snapshot = top_processes.snapshot.clone().unwrap();
+
+        // 150 ticks in 2 seconds is 75% of one core, so 37.5% of 2 cores
+        assert_eq!(pids(&snapshot.top_cpu), vec![PID_A, PID_C]);
+        assert_eq!(snapshot.top_cpu[0].cpu_usage_percent, 37.5);
+        assert_eq!(snapshot.top_cpu[1].cpu_usage_percent, 25.0);
+        // Processes with equal usage are ordered by pid
+        assert_eq!(pids(&snapshot.top_memory), vec![PID_C, PID_B]);
+        assert_eq!(snapshot.top_memory[0].memory_bytes, 1000 * 4096);
+        assert_eq!(snapshot.top_memory[1].name, "-");
+    }
+
+    #[test]
+    fn test_usage_is_reset_after_snapshot() {
+        let first = stat_files(&[(PID_A, 0, 0, 1), (PID_B, 0, 0, 1)]);
+        let mut processes = ProcessesStatsCache::from_stat_files(&as_stat_files(&first), 10.0);
+        let mut top_processes = TopProcessesStatsCache::new(1, 1, 1);
+
+        let second = stat_files(&[(PID_A, 100, 0, 1), (PID_B, 50, 0, 1)]);
+        processes.refresh_from_stat_files(&as_stat_files(&second), 11.0);
+        top_processes.refresh(&processes);
+        let snapshot = top_processes.snapshot.clone().unwrap();
+        assert_eq!(pids(&snapshot.top_cpu), vec![PID_A]);
+        assert_eq!(snapshot.top_cpu[0].cpu_usage_percent, 100.0);
+
+        let third = stat_files(&[(PID_A, 100, 0, 1), (PID_B, 100, 0, 1)]);
+        processes.refresh_from_stat_files(&as_stat_files(&third), 12.0);
+        top_processes.refresh(&processes);
+        let snapshot = top_processes.snapshot.clone().unwrap();
+        assert_eq!(pids(&snapshot.top_cpu), vec![PID_B]);
+        assert_eq!(snapshot.top_cpu[0].cpu_usage_percent, 50.0);
+    }
+
+    #[test]
+    fn test_reused_pid_does_not_inherit_usage_of_ended_process() {
+        let first = stat_files(&[(PID_A, 0, 0, 1), (PID_B, 0, 0, 1)]);
+        let mut processes = ProcessesStatsCache::from_stat_files(&as_stat_files(&first), 10.0);
+        let mut top_processes = TopProcessesStatsCache::new(2, 3, 1);
+
+        let second = stat_files(&[(PID_A, 90, 0, 1), (PID_B, 30, 0, 1)]);
+        processes.refresh_from_stat_files(&as_stat_files(&second), 11.0);
+        top_processes.refresh(&processes);
+
+        // Process A ended and its pid was reused by new process, which started after previous refresh
+        let third = stat_files(&[(PID_A, 10, 1_150, 1), (PID_B, 60, 0, 1)]);
+        processes.refresh_from_stat_files(&as_stat_files(&third), 12.0);
+        top_processes.refresh(&processes);
+
+        let fourth = stat_files(&[(PID_A, 40, 1_150, 1), (PID_B, 90, 0, 1)]);
+        processes.refresh_from_stat_files(&as_stat_files(&fourth), 13.0);
+        top_processes.refresh(&processes);
+        let snapshot = top_processes.snapshot.clone().unwrap();
+
+        // New process A used 40 ticks in 3 seconds, ended one used 90 ticks which are lost
+        assert_eq!(pids(&snapshot.top_cpu), vec![PID_B, PID_A]);
+        assert_eq!(snapshot.top_cpu[0].cpu_usage_percent, 30.0);
+        assert_eq!(snapshot.top_cpu[1].cpu_usage_percent, 40.0 / 3.0);
+    }
+
+    #[test]
+    fn test_first_refresh_has_no_cpu_usage() {
+        let first = stat_files(&[(PID_A, 1000, 0, 1), (PID_B, 0, 0, 2)]);
+        let processes = ProcessesStatsCache::from_stat_files(&as_stat_files(&first), 10.0);
+        let mut top_processes = TopProcessesStatsCache::new(5, 1, 1);
+        top_processes.refresh(&processes);
+        let snapshot = top_processes.snapshot.clone().unwrap();
+        assert!(snapshot.top_cpu.iter().all(|e| e.cpu_usage_percent == 0.0));
+        assert_eq!(pids(&snapshot.top_memory), vec![PID_B, PID_A]);
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Error};
+
+use crate::model::{find_uid, find_user_name};
+use crate::stats::processes::ProcessesStatsCache;
+use crate::stats::sanitize_item_name;
+
+#[derive(Default, Debug, Clone)]
+pub struct UserUsage {
+    pub name: String,
+    pub cpu_usage_percent: f64,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct UsersStatsCache {
+    // Empty means that users with the highest cpu usage are collected
+    selected_uids: Vec<u32>,
+    top_users: usize,
+    core_count: usize,
+    // Names are found only once for every user which is saved
+    user_names: HashMap<u32, String>,
+    // In order of selected users or sorted by cpu usage
+    pub usages: Vec<UserUsage>,
+}
+
+impl UsersStatsCache {
+    pub fn new(users_to_check: &[String], top_users: usize, core_count: usize) -> Result<Self, Error> {
+        let selected_uids = users_to_check
+            .iter()
+            .map(|user| find_uid(user).map(|uid| *uid).context(format!("Failed to find user {user}")))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(UsersStatsCache {
+            selected_uids,
+            top_users,
+            core_count,
+            user_names: HashMap::new(),
+            usages: Vec::new(),
+        })
+    }
+
+    pub fn refresh(&mut self, processes: &ProcessesStatsCache) {
+        let mut per_uid_usage: HashMap<u32, (u64, u64)> = HashMap::new();
+        for (pid, process) in &processes.processes {
+            let Some(uid) = process.uid else {
+                continue;
+            };
+            let usage = per_uid_usage.entry(uid).or_default();
+            usage.0 += processes.used_ticks(*pid).unwrap_or(0);
+            usage.1 += process.stat.rss_pages;
+        }
+
+        let uids = if self.selected_uids.is_empty() {
+            let mut uids = per_uid_usage.keys().copied().collect::<Vec<_>>();
+            uids.sort_by_key(|uid| (std::cmp::Reverse(per_uid_usage[uid]), *uid));
+            uids.truncate(self.top_users);
+            uids
+        } else {
+            self.selected_uids.clone()
+        };
+
+        // 100% means that all cores are fully used, the same as for processes
+        let elapsed_seconds = processes.elapsed_seconds();
+        self.usages = uids
+            .into_iter()
+            .map(|uid| {
+                let (used_ticks, rss_pages) = per_uid_usage.get(&uid).copied().unwrap_or_default();
+                UserUsage {
+                    name: self
+                        .user_names
+                        .entry(uid)
+                        .or_insert_with(|| find_user_name(uid).map_or_else(|| uid.to_string(), |e| sanitize_item_name(&e)))
+                        .clone(),
+                    cpu_usage_percent: elapsed_seconds.map_or(0.0, |elapsed_seconds| {
+                        processes.cpu_usage_percent(used_ticks, elapsed_seconds) / self.core_count as f64
+                    }),
+                    memory_bytes: rss_pages * processes.page_size,
+                }
+            })
+            .collect();
+    }
+}
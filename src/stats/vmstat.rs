@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Error};
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct VmStatRates {
+    pub minor_page_faults_per_second: f64,
+    pub major_page_faults_per_second: f64,
+    pub swap_in_pages_per_second: f64,
+    pub swap_out_pages_per_second: f64,
+    pub page_scan_per_second: f64,
+    pub page_steal_per_second: f64,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct VmStatStatsCache {
+    previous_values: HashMap<String, u64>,
+    // None on first tick or when time between ticks couldn't be measured
+    pub rates: Option<VmStatRates>,
+    // Counter of all oom kills since boot, so it is not converted to rate, to not hide single kills
+    pub oom_kills: Option<u64>,
+}
+
+impl VmStatStatsCache {
+    pub fn new() -> Result<Self, Error> {
+        let previous_values = read_vmstat(Path::new("/proc/vmstat"))?;
+        Ok(VmStatStatsCache {
+            oom_kills: previous_values.get("oom_kill").copied(),
+            previous_values,
+            rates: None,
+        })
+    }
+
+    pub fn refresh(&mut self, elapsed: Duration) -> Result<(), Error> {
+        let current_values = read_vmstat(Path::new("/proc/vmstat"))?;
+        self.update_values(current_values, elapsed);
+        Ok(())
+    }
+
+    fn update_values(&mut self, current_values: HashMap<String, u64>, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+
+        self.rates = if seconds > 0.0 {
+            let rate = |keys: &[&str]| {
+                keys.iter()
+                    .map(|key| {
+                        let current = current_values.get(*key).copied().unwrap_or(0);
+                        let previous = self.previous_values.get(*key).copied().unwrap_or(0);
+                        current.saturating_sub(previous)
+                    })
+                    .sum::<u64>() as f64
+                    / seconds
+            };
+            let major_page_faults_per_second = rate(&["pgmajfault"]);
+            Some(VmStatRates {
+                // pgfault counts both minor and major faults
+                minor_page_faults_per_second: (rate(&["pgfault"]) - major_page_faults_per_second).max(0.0),
+                major_page_faults_per_second,
+                swap_in_pages_per_second: rate(&["pswpin"]),
+                swap_out_pages_per_second: rate(&["pswpout"]),
+                // Older kernels don't contain khugepaged and proactive counters, then they are just treated as 0
+                page_scan_per_second: rate(&["pgscan_kswapd", "pgscan_direct", "pgscan_khugepaged", "pgscan_proactive"]),
+                page_steal_per_second: rate(&["pgsteal_kswapd", "pgsteal_direct", "pgsteal_khugepaged", "pgsteal_proactive"]),
+            })
+        } else {
+            None
+        };
+        self.oom_kills = current_values.get("oom_kill").copied();
+        self.previous_values = current_values;
+    }
+}
+
+// Line looks like - pgmajfault 670
+fn read_vmstat(vmstat_path: &Path) -> Result<HashMap<String, u64>, Error> {
+    let content = fs::read_to_string(vmstat_path).with_context(|| format!("Failed to read {}", vmstat_path.display()))?;
+
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse::<u64>().ok()?))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::TestDir;
+
+    #[test]
+    fn test_parsing_skips_malformed_lines() {
+        let test_dir = TestDir::new("vmstat");
+        test_dir.write(
+            "vmstat",
+            "pgfault 1000\npgmajfault 10\noom_kill abc\nbroken_line\npswpin -5\n\nnr_free_pages 12345\n",
+        );
+
+        let values = read_vmstat(&test_dir.path.join("vmstat")).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["pgfault"], 1000);
+        assert_eq!(values["pgmajfault"], 10);
+        assert_eq!(values["nr_free_pages"], 12345);
+    }
+
+    #[test]
+    fn test_missing_vmstat() {
+        let test_dir = TestDir::new("vmstat");
+        assert!(read_vmstat(&test_dir.path.join("vmstat")).is_err());
+    }
+
+    #[test]
+    fn test_rates_between_ticks() {
+        let test_dir = TestDir::new("vmstat");
+        test_dir.write(
+            "first",
+            "pgfault 1000\npgmajfault 10\npswpin 0\npswpout 0\npgscan_kswapd 100\npgscan_direct 50\npgsteal_kswapd 80\noom_kill 1\n",
+        );
+        test_dir.write(
+            "second",
+            "pgfault 1500\npgmajfault 30\npswpin 4\npswpout 8\npgscan_kswapd 300\npgscan_direct 70\npgsteal_kswapd 180\noom_kill 2\n",
+        );
+
+        let previous_values = read_vmstat(&test_dir.path.join("first")).unwrap();
+        let mut vmstat_stats = VmStatStatsCache {
+            oom_kills: previous_values.get("oom_kill").copied(),
+            previous_values,
+            rates: None,
+        };
+        vmstat_stats.update_values(read_vmstat(&test_dir.path.join("second")).unwrap(), Duration::from_secs(2));
+
+        let rates = vmstat_stats.rates.unwrap();
+        assert_eq!(rates.minor_page_faults_per_second, 240.0);
+        assert_eq!(rates.major_page_faults_per_second, 10.0);
+        assert_eq!(rates.swap_in_pages_per_second, 2.0);
+        assert_eq!(rates.swap_out_pages_per_second, 4.0);
+        assert_eq!(rates.page_scan_per_second, 110.0);
+        assert_eq!(rates.page_steal_per_second, 50.0);
+        assert_eq!(vmstat_stats.oom_kills, Some(2));
+
+        vmstat_stats.update_values(HashMap::new(), Duration::ZERO);
+        assert!(vmstat_stats.rates.is_none());
+        assert_eq!(vmstat_stats.oom_kills, None);
+    }
+}